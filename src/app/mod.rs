@@ -28,9 +28,17 @@ fn main() -> Result<(), Error> {
 ```
 */
 
-use std::{borrow::Cow, fs::File, path::PathBuf};
-
-use crate::prelude::*;
+use std::{
+    borrow::Cow,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    prelude::*,
+    utils::{list_style::ListStyle, mdx::MdxBlocks, numbering::Numbering, slug::Transliterated},
+};
+use anyhow::Context;
 
 pub struct App<'a> {
     pub args: Cow<'a, Opt>,
@@ -43,7 +51,7 @@ impl<'a> App<'a> {
     pub fn init() -> Result<Self, Error> {
         let args: Cow<'a, Opt> = Cow::Owned(Opt::parse());
         let path = args.input.path().path().to_path_buf();
-        let taboc = Taboc::new(File::open(&path)?, args.max_depth);
+        let taboc = Self::make_taboc(&args, &path)?;
 
         Ok(Self { args, path, taboc })
     }
@@ -52,7 +60,7 @@ impl<'a> App<'a> {
     #[allow(dead_code)]
     pub fn from_args(args: &'a Opt) -> Result<Self, Error> {
         let path = args.input.path().path().to_path_buf();
-        let table_of_contents = Taboc::new(File::open(&path)?, args.max_depth);
+        let table_of_contents = Self::make_taboc(args, &path)?;
 
         Ok(Self {
             args: Cow::Borrowed(args),
@@ -61,10 +69,58 @@ impl<'a> App<'a> {
         })
     }
 
+    /// Make the table of contents generator based on the `args`.
+    fn make_taboc(args: &Opt, path: &Path) -> Result<Taboc, Error> {
+        let taboc = Taboc::new(File::open(path)?, args.max_depth)
+            .with_path(path)
+            .with_min_depth(args.min_depth)
+            .with_skip_title(args.skip_title)
+            .with_include(args.include.clone())
+            .with_exclude(args.exclude.clone())
+            .with_front_matter_title(args.front_matter_title)
+            .with_decoding(args.encoding)
+            .with_backend(args.backend)
+            .with_flavor(args.flavor)
+            .with_mdx(args.mdx || MdxBlocks::is_mdx(path))
+            .with_list_style(ListStyle {
+                bullet: args.bullet,
+                ordered: args.ordered,
+                indent: args.indent,
+                nested: !args.flat,
+            })
+            .with_numbering((!args.numbering.is_empty()).then(|| Numbering {
+                styles: args.numbering.clone(),
+                start_level: args.numbering_start,
+            }));
+        let taboc = if args.ascii_slugs {
+            taboc.with_slugger(Transliterated(args.slug_style))
+        } else {
+            taboc.with_slugger(args.slug_style)
+        };
+
+        let enabled = args.enable.iter().map(|ext| (*ext, true));
+        let disabled = args.disable.iter().map(|ext| (*ext, false));
+        Ok(enabled
+            .chain(disabled)
+            .fold(taboc, |taboc, (ext, enabled)| {
+                taboc.with_extension(ext, enabled)
+            }))
+    }
+
+    /// Make the table of contents. Errors name the file they're from.
+    fn parse(&self) -> Result<String, Error> {
+        self.taboc.parse().with_context(|| {
+            format!(
+                "Couldn't make the table of contents of `{}`.",
+                self.path.display()
+            )
+        })
+    }
+
     /// Run the main application logic.
     pub fn run(&self) -> Result<(), Error> {
         if self.args.no_file_update {
-            println!("{}", self.taboc.parse()?);
+            println!("{}", self.parse()?);
             return Ok(());
         }
 
@@ -74,7 +130,15 @@ impl<'a> App<'a> {
         }
 
         self.taboc
-            .write_to_file(&self.path, &self.taboc.parse()?, self.args.update_existing)?;
+            .write_to_file(&self.path, &self.parse()?, self.args.update_existing)?;
+        if self.args.insert_ids {
+            self.taboc.insert_ids(&self.path).with_context(|| {
+                format!(
+                    "Couldn't insert the heading ids into `{}`.",
+                    self.path.display()
+                )
+            })?;
+        }
 
         Ok(())
     }
@@ -74,7 +74,7 @@
 )]
 #![warn(clippy::explicit_into_iter_loop, reason = "Readability.")]
 #![deny(clippy::redundant_else, reason = "Readability.")]
-#![deny(clippy::string_to_string, reason = "Redundant.")]
+#![deny(clippy::implicit_clone, reason = "Redundant.")]
 #![deny(clippy::get_unwrap, reason = "Prefer direct indexing for conciseness.")]
 
 /*!
@@ -8,7 +8,7 @@ WARN: This re-exports potentially ambiguous types like [`anyhow::Error`], [`clap
 
 pub use crate::{
     app::App,
-    utils::{args::Opt, toc::Taboc},
+    utils::{args::Opt, heading::Heading, toc::Taboc},
 };
 
 #[cfg(feature = "git")]
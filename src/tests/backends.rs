@@ -0,0 +1,86 @@
+use crate::{
+    prelude::*,
+    utils::{backend::Backend, decoding::Decoding, mdx::MdxBlocks},
+};
+
+/// The fixtures the backends are known to disagree on, along with the reason.
+const KNOWN_DIFFERENCES: &[(&str, &str)] = &[
+    (
+        "explicit_ids.md",
+        "The parser reads any trailing `{...}` as an attribute block and the attribute block of a \
+         setext heading isn't a part of its raw text.",
+    ),
+    (
+        "indentation.md",
+        "The scanner doesn't find headings in list items.",
+    ),
+];
+
+/// Find the headings of a fixture and make its table of contents with a backend.
+fn run(path: &std::path::Path, backend: Backend) -> Result<(Vec<Heading>, String), Error> {
+    let taboc = Taboc::new(std::fs::File::open(path)?, 6)
+        .with_decoding(Decoding::Lossy)
+        .with_mdx(MdxBlocks::is_mdx(path))
+        .with_backend(backend);
+
+    Ok((taboc.headings()?.collect::<Result<_, _>>()?, taboc.parse()?))
+}
+
+/// Describe where the backends differ on a fixture. Empty if they agree.
+fn differences(path: &std::path::Path) -> Result<Vec<String>, Error> {
+    let (scanner, scanner_toc) = run(path, Backend::Scanner)?;
+    let (parser, parser_toc) = run(path, Backend::CommonMark)?;
+
+    let mut res = Vec::new();
+    for i in 0..scanner.len().max(parser.len()) {
+        match (scanner.get(i), parser.get(i)) {
+            (Some(s), Some(p)) if s == p => {}
+            (s, p) => res.push(format!("  scanner: {s:?}\n  parser:  {p:?}")),
+        }
+    }
+    if res.is_empty() && scanner_toc != parser_toc {
+        res.push(format!(
+            "  scanner: {scanner_toc:?}\n  parser:  {parser_toc:?}"
+        ));
+    }
+
+    Ok(res)
+}
+
+#[test]
+fn test_backend_differences() -> Result<(), Error> {
+    let mut fixtures = std::fs::read_dir(std::env::current_dir()?.join("mock_data"))?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<Result<Vec<_>, Error>>()?;
+    fixtures.sort();
+
+    let mut differing = Vec::new();
+    for path in fixtures
+        .iter()
+        .filter(|p| p.extension().is_some_and(|ext| ext == "md" || ext == "mdx"))
+    {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let differences = differences(path)?;
+        if differences.is_empty() {
+            continue;
+        }
+
+        let reason = KNOWN_DIFFERENCES
+            .iter()
+            .find(|(fixture, _)| *fixture == name)
+            .map_or("Unexpected.", |(_, reason)| reason);
+        println!("{name}: {reason}\n{}", differences.join("\n"));
+        differing.push(name);
+    }
+
+    let known = KNOWN_DIFFERENCES
+        .iter()
+        .map(|(fixture, _)| (*fixture).to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(known, differing);
+
+    Ok(())
+}
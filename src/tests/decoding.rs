@@ -0,0 +1,78 @@
+use crate::{prelude::*, utils::decoding::Decoding};
+
+#[test]
+fn test_strict_error() -> Result<(), Error> {
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/latin1.md"))?;
+
+    let Err(e) = Taboc::new(file, 6).parse() else {
+        return Err(anyhow!("Expected an invalid UTF-8 error."));
+    };
+    assert_eq!(e.to_string(), "Invalid UTF-8 on line 5 at byte offset 68.");
+
+    let path = std::env::current_dir()?.join("mock_data/latin1.md");
+    let Err(e) = Taboc::new(std::fs::File::open(&path)?, 6)
+        .with_path(&path)
+        .parse()
+    else {
+        return Err(anyhow!("Expected an invalid UTF-8 error."));
+    };
+    assert_eq!(
+        e.to_string(),
+        format!(
+            "Invalid UTF-8 in `{}` on line 5 at byte offset 68.",
+            path.display()
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_lossy() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Latin-1](#latin-1)
+  - [Caf\u{FFFD}](#caf)
+    - [R\u{FFFD}sum\u{FFFD}](#rsum)
+  - [Na\u{FFFD}ve Heading](#nave-heading)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/latin1.md"))?;
+
+    assert_eq!(
+        expected,
+        Taboc::new(file, 6).with_decoding(Decoding::Lossy).parse()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_legacy_encoding() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Latin-1](#latin-1)
+  - [Café](#café)
+    - [Résumé](#résumé)
+  - [Naïve Heading](#naïve-heading)";
+
+    let source = std::fs::read(std::env::current_dir()?.join("mock_data/latin1.md"))?;
+    let path = std::env::temp_dir().join("taboc_latin1.md");
+    std::fs::write(&path, &source)?;
+
+    let taboc = Taboc::new(std::fs::File::open(&path)?, 6).with_decoding("latin1".parse()?);
+    assert_eq!(expected, taboc.parse()?);
+
+    // The table of contents is written in the encoding of the document.
+    taboc.write_to_file(&path, &taboc.parse()?, false)?;
+    let written = std::fs::read(&path)?;
+    assert!(written
+        .windows(b"- [Caf\xE9](#caf\xE9)".len())
+        .any(|w| w == b"- [Caf\xE9](#caf\xE9)"));
+    assert!(std::str::from_utf8(&written).is_err());
+
+    Ok(())
+}
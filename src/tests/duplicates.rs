@@ -0,0 +1,52 @@
+use crate::{prelude::*, utils::slug::SlugStyle};
+
+#[test]
+fn test_duplicate_headings() -> Result<(), Error> {
+    // GitHub only counts the repeats of the same slug, so `Example 1` gets the same anchor as the
+    // second `Example`.
+    let expected = "
+
+## Table of contents
+
+- [Duplicates](#duplicates)
+  - [First API](#first-api)
+    - [Example](#example)
+  - [Second API](#second-api)
+    - [Example](#example-1)
+      - [Table of contents](#table-of-contents-1)
+    - [Example 1](#example-1)
+    - [Example](#example-2)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/duplicates.md"))?;
+
+    assert_eq!(expected, Taboc::new(file, 6).parse()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_collisions() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Duplicates](#duplicates)
+  - [First API](#first-api)
+    - [Example](#example)
+  - [Second API](#second-api)
+    - [Example](#example-1)
+      - [Table of contents](#table-of-contents-1)
+    - [Example 1](#example-1-1)
+    - [Example](#example-2)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/duplicates.md"))?;
+
+    assert_eq!(
+        expected,
+        Taboc::new(file, 6)
+            .with_slugger(SlugStyle::GitLab)
+            .parse()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use crate::prelude::*;
+
+#[test]
+fn test_explicit_ids() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Explicit IDs](#explicit-ids)
+  - [Setup](#setup-guide)
+  - [Configuration](#cfg)
+  - [Pandoc Style](#pandoc)
+  - [Braces {that aren't attributes}](#braces-that-arent-attributes)
+  - [Setup](#setup)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/explicit_ids.md"))?;
+
+    assert_eq!(expected, Taboc::new(file, 6).parse()?);
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use crate::{
+    prelude::*,
+    utils::flavor::{Extension, Flavor},
+};
+
+fn extensions_mock() -> Result<std::fs::File, Error> {
+    Ok(std::fs::File::open(
+        std::env::current_dir()?.join("mock_data/extensions.md"),
+    )?)
+}
+
+#[test]
+fn test_gfm_math() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Extension Blocks](#extension-blocks)
+  - [Display Math](#display-math)
+- [Not math anymore](#not-math-anymore)
+  - [Fenced Divs](#fenced-divs)
+- [A heading in a div](#a-heading-in-a-div)
+  - [A nested div](#a-nested-div)
+- [Still in the div](#still-in-the-div)
+  - [Liquid](#liquid)
+- [Raw template text](#raw-template-text)
+- [A shell comment](#a-shell-comment)
+- [Commented out](#commented-out)
+  - [The End](#the-end)";
+
+    assert_eq!(expected, Taboc::new(extensions_mock()?, 6).parse()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_inline_math() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Inline Math](#inline-math)
+  - [After](#after)
+  - [Display Math](#display-math)
+  - [Another](#another)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/inline_math.md"))?;
+    assert_eq!(expected, Taboc::new(file, 6).parse()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_flavors() -> Result<(), Error> {
+    let pandoc = "
+
+## Table of contents
+
+- [Extension Blocks](#extension-blocks)
+  - [Display Math](#display-math)
+- [Not math anymore](#not-math-anymore)
+  - [Fenced Divs](#fenced-divs)
+  - [Liquid](#liquid)
+- [Raw template text](#raw-template-text)
+- [A shell comment](#a-shell-comment)
+- [Commented out](#commented-out)
+  - [The End](#the-end)";
+    let jekyll = "
+
+## Table of contents
+
+- [Extension Blocks](#extension-blocks)
+  - [Display Math](#display-math)
+- [Not math anymore](#not-math-anymore)
+  - [Fenced Divs](#fenced-divs)
+- [A heading in a div](#a-heading-in-a-div)
+  - [A nested div](#a-nested-div)
+- [Still in the div](#still-in-the-div)
+  - [Liquid](#liquid)
+  - [The End](#the-end)";
+
+    assert_eq!(
+        pandoc,
+        Taboc::new(extensions_mock()?, 6)
+            .with_flavor(Flavor::Pandoc)
+            .parse()?
+    );
+    assert_eq!(
+        jekyll,
+        Taboc::new(extensions_mock()?, 6)
+            .with_flavor(Flavor::Jekyll)
+            .parse()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_toggled_extensions() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Extension Blocks](#extension-blocks)
+  - [Display Math](#display-math)
+- [A LaTeX comment, not a heading](#a-latex-comment-not-a-heading)
+- [Not math anymore](#not-math-anymore)
+  - [Fenced Divs](#fenced-divs)
+  - [Liquid](#liquid)
+- [Raw template text](#raw-template-text)
+- [A shell comment](#a-shell-comment)
+- [Commented out](#commented-out)
+  - [The End](#the-end)";
+
+    let taboc = Taboc::new(extensions_mock()?, 6)
+        .with_flavor(Flavor::CommonMark)
+        .with_extension(Extension::FencedDivs, true);
+    assert_eq!(expected, taboc.parse()?);
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+#[test]
+fn test_fenced_code_blocks() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Fences](#fences)
+  - [Nested fence](#nested-fence)
+  - [Tilde fence](#tilde-fence)
+  - [Mismatched closing fence](#mismatched-closing-fence)
+  - [Info string](#info-string)
+  - [Inline code](#inline-code)
+  - [Unclosed fence](#unclosed-fence)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/fences.md"))?;
+
+    assert_eq!(expected, Taboc::new(file, 6).parse()?);
+
+    Ok(())
+}
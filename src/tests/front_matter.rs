@@ -0,0 +1,109 @@
+use crate::prelude::*;
+
+#[test]
+fn test_yaml_front_matter() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Front Matter](#front-matter)
+  - [Heading Two](#heading-two)
+    - [Heading Three](#heading-three)
+  - [Heading Two Number 2](#heading-two-number-2)";
+
+    let path = std::env::current_dir()?.join("mock_data/front_matter.md");
+
+    assert_eq!(
+        expected,
+        Taboc::new(std::fs::File::open(&path)?, 6)
+            .with_front_matter_title(true)
+            .parse()?
+    );
+    assert!(!Taboc::new(std::fs::File::open(&path)?, 6)
+        .parse()?
+        .contains("Front Matter"));
+
+    Ok(())
+}
+
+#[test]
+fn test_toml_front_matter() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Title](#title)
+  - [Heading Two](#heading-two)";
+
+    let file =
+        std::fs::File::open(std::env::current_dir()?.join("mock_data/front_matter.toml.md"))?;
+
+    // The document already has a title so the front matter one isn't used.
+    assert_eq!(
+        expected,
+        Taboc::new(file, 6).with_front_matter_title(true).parse()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_write_after_front_matter() -> Result<(), Error> {
+    let source =
+        std::fs::read_to_string(std::env::current_dir()?.join("mock_data/front_matter.md"))?;
+    let path = std::env::temp_dir().join("taboc_front_matter.md");
+    std::fs::write(&path, &source)?;
+
+    let taboc = Taboc::new(std::fs::File::open(&path)?, 6);
+    taboc.write_to_file(&path, &taboc.parse()?, false)?;
+
+    let (front_matter, rest) = source.split_at(source.find("\n\n## Heading Two\n").unwrap_or(0));
+    let expected = format!(
+        "{front_matter}
+
+## Table of contents
+
+- [Heading Two](#heading-two)
+  - [Heading Three](#heading-three)
+- [Heading Two Number 2](#heading-two-number-2){rest}"
+    );
+    assert_eq!(expected, std::fs::read_to_string(&path)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_front_matter_title_duplicate() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Overview](#overview)
+  - [Overview](#overview-1)
+  - [Usage](#usage)";
+
+    let file =
+        std::fs::File::open(std::env::current_dir()?.join("mock_data/front_matter_duplicate.md"))?;
+    assert_eq!(
+        expected,
+        Taboc::new(file, 6).with_front_matter_title(true).parse()?
+    );
+
+    // The title is still rendered when it isn't a part of the table of contents.
+    let file =
+        std::fs::File::open(std::env::current_dir()?.join("mock_data/front_matter_duplicate.md"))?;
+    assert_eq!(
+        "
+
+## Table of contents
+
+- [Overview](#overview-1)
+- [Usage](#usage)",
+        Taboc::new(file, 6)
+            .with_front_matter_title(true)
+            .with_skip_title(true)
+            .parse()?
+    );
+
+    Ok(())
+}
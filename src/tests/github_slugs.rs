@@ -0,0 +1,47 @@
+use crate::{prelude::*, utils::backend::Backend};
+
+/// Check the GitHub anchors of the golden table (`heading<TAB>anchor` lines) with a backend. The
+/// headings are written to a document in order, so the repeated ones are disambiguated.
+fn check(backend: Backend, name: &str) -> Result<(), Error> {
+    let table =
+        std::fs::read_to_string(std::env::current_dir()?.join("mock_data/github_slugs.tsv"))?;
+    let rows = table
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.is_empty())
+        .map(|line| {
+            line.split_once('\t')
+                .ok_or(anyhow!("Invalid row: `{line}`."))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let source = rows
+        .iter()
+        .map(|(heading, _)| format!("## {heading}\n\n"))
+        .collect::<String>();
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, source)?;
+
+    let slugs = Taboc::new(std::fs::File::open(&path)?, 6)
+        .with_backend(backend)
+        .headings()?
+        .map(|heading| Ok(heading?.slug))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    assert_eq!(rows.len(), slugs.len());
+    for ((heading, expected), slug) in rows.iter().zip(&slugs) {
+        assert_eq!(expected, slug, "{heading}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_github_slugs_golden() -> Result<(), Error> {
+    check(Backend::Scanner, "taboc_github_slugs.md")
+}
+
+#[test]
+#[cfg(feature = "commonmark")]
+fn test_github_slugs_golden_commonmark() -> Result<(), Error> {
+    check(Backend::CommonMark, "taboc_github_slugs_commonmark.md")
+}
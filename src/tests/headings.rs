@@ -0,0 +1,43 @@
+use crate::prelude::*;
+
+#[test]
+fn test_heading_spans() -> Result<(), Error> {
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/setext.md"))?;
+    let taboc = Taboc::new(file, 6);
+    let headings = taboc.headings()?.collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(headings.len(), 5);
+    assert_eq!(
+        headings[0],
+        Heading {
+            level: 1,
+            raw: "Setext Title".to_owned(),
+            text: "Setext Title".to_owned(),
+            slug: "setext-title".to_owned(),
+            line: 5,
+            range: 23..48,
+            omitted: false,
+        }
+    );
+    assert_eq!(
+        headings[2],
+        Heading {
+            level: 2,
+            raw: "A paragraph that spans multiple lines".to_owned(),
+            text: "A paragraph that spans multiple lines".to_owned(),
+            slug: "a-paragraph-that-spans-multiple-lines".to_owned(),
+            line: 14,
+            range: 189..230,
+            omitted: false,
+        }
+    );
+    assert_eq!(
+        (headings[3].line, headings[3].range.clone()),
+        (29, 354..377)
+    );
+
+    // The iterator starts from the beginning of the file every time.
+    assert_eq!(taboc.headings()?.count(), 5);
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use crate::prelude::*;
+
+#[test]
+fn test_html_blocks() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [HTML](#html)
+  - [Heading Two](#heading-two)
+  - [Heading Inside Details](#heading-inside-details)
+  - [Heading Two Number 2](#heading-two-number-2)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/html.md"))?;
+
+    assert_eq!(expected, Taboc::new(file, 6).parse()?);
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use crate::prelude::*;
+
+#[test]
+fn test_indentation() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Indentation](#indentation)
+  - [Indented by three spaces](#indented-by-three-spaces)
+  - [Separated by a tab](#separated-by-a-tab)
+  - [A heading with list item indentation](#a-heading-with-list-item-indentation)
+  - [Back at the top level](#back-at-the-top-level)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/indentation.md"))?;
+
+    assert_eq!(expected, Taboc::new(file, 6).parse()?);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use crate::prelude::*;
+
+#[test]
+fn test_inline_markdown() -> Result<(), Error> {
+    let expected = r"
+
+## Table of contents
+
+- [Inline Markdown](#inline-markdown)
+  - [The Vec\<T\> type](#the-vect-type)
+  - [Dereference \*ptr\*](#dereference-ptr)
+  - [The \<b\> tag \& entities](#the-b-tag--entities)
+  - [Bold and struck links](#bold-and-struck-links)
+  - [snake\_case \[brackets\]](#snake_case-brackets)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/inline.md"))?;
+
+    assert_eq!(expected, Taboc::new(file, 6).parse()?);
+
+    Ok(())
+}
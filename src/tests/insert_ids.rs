@@ -0,0 +1,87 @@
+use crate::{
+    prelude::*,
+    utils::{
+        decoding::Decoding,
+        slug::{GitHub, Transliterated},
+    },
+};
+
+#[test]
+fn test_insert_ids() -> Result<(), Error> {
+    let source = std::fs::read_to_string(std::env::current_dir()?.join("mock_data/insert_ids.md"))?;
+    let path = std::env::temp_dir().join("taboc_insert_ids.md");
+    std::fs::write(&path, source)?;
+
+    let taboc = Taboc::new(std::fs::File::open(&path)?, 6).with_slugger(Transliterated(GitHub));
+    let toc = taboc.parse()?;
+    assert_eq!(4, taboc.insert_ids(&path)?);
+
+    let expected = "# <a id=\"grosse\"></a>Größe ##
+
+## <a id=\"privet-\"></a>Привет <!-- comment -->
+
+<a id=\"setext-heading-with-two-lines\"></a>Setext Heading
+with two lines
+--------------
+
+## Already <a id=\"custom\"></a>
+
+### <a id=\"ri-ben-yu\"></a>日本語
+";
+    assert_eq!(expected, std::fs::read_to_string(&path)?);
+
+    // The inserted ids are used verbatim, whatever the slugger is.
+    let taboc = Taboc::new(std::fs::File::open(&path)?, 6);
+    assert_eq!(toc, taboc.parse()?);
+    assert_eq!(0, taboc.insert_ids(&path)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_insert_ids_lossy() -> Result<(), Error> {
+    let path = std::env::temp_dir().join("taboc_insert_ids_lossy.md");
+    std::fs::write(
+        &path,
+        b"Caf\xE9 \xE9\xE9\n\n# R\xE9sum\xE9\n\n  \xE9 Setext\n  ---\n",
+    )?;
+
+    let taboc = Taboc::new(std::fs::File::open(&path)?, 6).with_decoding(Decoding::Lossy);
+    assert_eq!(2, taboc.insert_ids(&path)?);
+
+    // The invalid bytes are kept, and the ids are right before the text of the headings.
+    let expected = b"Caf\xE9 \xE9\xE9
+
+# <a id=\"rsum\"></a>R\xE9sum\xE9
+
+  <a id=\"-setext\"></a>\xE9 Setext
+  ---
+";
+    assert_eq!(&expected[..], &std::fs::read(&path)?[..]);
+
+    Ok(())
+}
+
+#[test]
+fn test_ascii_slugs() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Größe](#grosse)
+  - [Привет](#privet-)
+  - [Setext Heading with two lines](#setext-heading-with-two-lines)
+  - [Already](#custom)
+    - [日本語](#ri-ben-yu)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/insert_ids.md"))?;
+
+    assert_eq!(
+        expected,
+        Taboc::new(file, 6)
+            .with_slugger(Transliterated(GitHub))
+            .parse()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::prelude::*;
+
+const EXPECTED: &str = "
+
+## Table of contents
+
+- [Line Endings](#line-endings)
+  - [Heading Two](#heading-two)
+    - [Heading Three](#heading-three)
+  - [Heading Two Number 2](#heading-two-number-2)";
+
+/// Write the mock file with `\r\n` line endings and a byte order mark to a temporary file.
+fn crlf_mock(name: &str) -> Result<(String, std::path::PathBuf), Error> {
+    let source =
+        std::fs::read_to_string(std::env::current_dir()?.join("mock_data/line_endings.md"))?;
+    let source = format!("\u{feff}{}", source.replace('\n', "\r\n"));
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, &source)?;
+
+    Ok((source, path))
+}
+
+#[test]
+fn test_crlf_and_bom() -> Result<(), Error> {
+    let (_, path) = crlf_mock("taboc_crlf_parse.md")?;
+
+    assert_eq!(
+        EXPECTED,
+        Taboc::new(std::fs::File::open(&path)?, 6).parse()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_write_crlf() -> Result<(), Error> {
+    let (source, path) = crlf_mock("taboc_crlf_write.md")?;
+
+    let taboc = Taboc::new(std::fs::File::open(&path)?, 6);
+    taboc.write_to_file(&path, &taboc.parse()?, false)?;
+
+    let (head, rest) = source.split_at(source.find("\r\n\r\n## Heading Two").unwrap_or(0));
+    let expected = format!("{head}{}{rest}", EXPECTED.replace('\n', "\r\n"));
+    let written = std::fs::read_to_string(&path)?;
+    assert_eq!(expected, written);
+    assert_eq!(
+        written.matches('\n').count(),
+        written.matches("\r\n").count()
+    );
+
+    // Updating the table of contents keeps the document as it is.
+    taboc.write_to_file(&path, &taboc.parse()?, true)?;
+    assert_eq!(written, std::fs::read_to_string(&path)?);
+    assert!(taboc.write_to_file(&path, &taboc.parse()?, false).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use crate::{
+    prelude::*,
+    utils::list_style::{Bullet, Indent, ListStyle},
+};
+
+fn toc(list_style: ListStyle) -> Result<String, Error> {
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/duplicates.md"))?;
+
+    Taboc::new(file, 6).with_list_style(list_style).parse()
+}
+
+#[test]
+fn test_bullet_and_indent() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+* [Duplicates](#duplicates)
+    * [First API](#first-api)
+        * [Example](#example)
+    * [Second API](#second-api)
+        * [Example](#example-1)
+            * [Table of contents](#table-of-contents-1)
+        * [Example 1](#example-1)
+        * [Example](#example-2)";
+
+    let list_style = ListStyle {
+        bullet: Bullet::Asterisk,
+        indent: Some(Indent::Spaces(4)),
+        ..ListStyle::default()
+    };
+    assert_eq!(expected, toc(list_style)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_tab_indent() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
++ [Duplicates](#duplicates)
+\t+ [First API](#first-api)
+\t\t+ [Example](#example)
+\t+ [Second API](#second-api)
+\t\t+ [Example](#example-1)
+\t\t\t+ [Table of contents](#table-of-contents-1)
+\t\t+ [Example 1](#example-1)
+\t\t+ [Example](#example-2)";
+
+    let list_style = ListStyle {
+        bullet: Bullet::Plus,
+        indent: Some(Indent::Tab),
+        ..ListStyle::default()
+    };
+    assert_eq!(expected, toc(list_style)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_ordered() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+1. [Duplicates](#duplicates)
+   1. [First API](#first-api)
+      1. [Example](#example)
+   2. [Second API](#second-api)
+      1. [Example](#example-1)
+         1. [Table of contents](#table-of-contents-1)
+      2. [Example 1](#example-1)
+      3. [Example](#example-2)";
+
+    let list_style = ListStyle {
+        ordered: true,
+        ..ListStyle::default()
+    };
+    assert_eq!(expected, toc(list_style)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_flat() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Duplicates](#duplicates)
+- [First API](#first-api)
+- [Example](#example)
+- [Second API](#second-api)
+- [Example](#example-1)
+- [Table of contents](#table-of-contents-1)
+- [Example 1](#example-1)
+- [Example](#example-2)";
+
+    let list_style = ListStyle {
+        nested: false,
+        ..ListStyle::default()
+    };
+    assert_eq!(expected, toc(list_style)?);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use crate::prelude::*;
+
+#[test]
+fn test_opt_out_markers() -> Result<(), Error> {
+    // The omitted headings still have anchors, and the space before their marker comment is a
+    // part of them (`#omitted-`, `#omitted-from-`, `#omitted--1`).
+    let expected = "
+
+## Table of contents
+
+- [Markers](#markers)
+  - [Heading Two](#heading-two)
+  - [Omitted](#omitted)
+  - [Heading Two Number 2](#heading-two-number-2)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/markers.md"))?;
+
+    assert_eq!(expected, Taboc::new(file, 6).parse()?);
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::{prelude::*, utils::mdx::MdxBlocks};
+
+#[test]
+fn test_mdx() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [MDX](#mdx)
+  - [Installing with npm](#installing-with-npm)
+  - [Usage](#usage)";
+
+    let path = std::env::current_dir()?.join("mock_data/mdx.mdx");
+    assert!(MdxBlocks::is_mdx(&path));
+
+    let taboc = Taboc::new(std::fs::File::open(&path)?, 6).with_mdx(true);
+    assert_eq!(expected, taboc.parse()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_write_after_esm() -> Result<(), Error> {
+    let source = "import Tabs from '@theme/Tabs';
+export const meta = {
+  title: 'Hello',
+};
+
+## Heading
+";
+    let path = std::env::temp_dir().join("taboc_esm.mdx");
+    std::fs::write(&path, source)?;
+
+    let taboc = Taboc::new(std::fs::File::open(&path)?, 6).with_mdx(true);
+    taboc.write_to_file(&path, &taboc.parse()?, false)?;
+
+    let (esm, rest) = source.split_at(source.find("\n\n## Heading").unwrap_or(0));
+    let expected = format!(
+        "{esm}
+
+## Table of contents
+
+- [Heading](#heading){rest}"
+    );
+    assert_eq!(expected, std::fs::read_to_string(&path)?);
+
+    Ok(())
+}
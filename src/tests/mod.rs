@@ -1,2 +1,69 @@
 /// Mock test with a README file.
 mod readme;
+
+/// Mock test with setext (underlined) headings.
+mod setext;
+
+/// Mock test with CommonMark fenced code blocks.
+mod fences;
+
+/// Mock test with indented headings and indented code blocks.
+mod indentation;
+
+/// Mock tests with YAML and TOML front matter.
+mod front_matter;
+
+/// Mock test with HTML comments and HTML blocks.
+mod html;
+
+/// Mock test with repeated headings.
+mod duplicates;
+
+/// Mock test with explicit heading ids.
+mod explicit_ids;
+
+/// Mock test for the heading line numbers and byte ranges.
+mod headings;
+
+/// Mock tests for the heading selection rules.
+mod selection;
+
+/// Mock test with the table of contents opt-out markers.
+mod markers;
+
+/// Mock test with no first-level heading and skipped heading levels.
+mod skipped_levels;
+
+/// Mock tests with `\r\n` line endings and a byte order mark.
+mod line_endings;
+
+/// Mock tests with a Latin-1 encoded document.
+mod decoding;
+
+/// Runs both heading backends over the mock files and reports where they differ.
+#[cfg(feature = "commonmark")]
+mod backends;
+
+/// Mock tests with math blocks, fenced divs and Liquid blocks.
+mod extensions;
+
+/// Mock tests with an MDX document.
+mod mdx;
+
+/// Mock tests with the slug style presets.
+mod slugs;
+
+/// The golden table of GitHub anchors.
+mod github_slugs;
+
+/// Mock tests with ASCII slugs and explicit id insertion.
+mod insert_ids;
+
+/// Mock tests with the list styles of the table of contents.
+mod list_style;
+
+/// Mock tests with hierarchical section numbering.
+mod numbering;
+
+/// Mock tests with inline markdown (code spans, entities, emphasis) in the headings.
+mod inline;
@@ -0,0 +1,104 @@
+use crate::{
+    prelude::*,
+    utils::{
+        list_style::ListStyle,
+        numbering::{NumberStyle, Numbering},
+    },
+};
+
+fn toc(numbering: Numbering, list_style: ListStyle) -> Result<String, Error> {
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/numbering.md"))?;
+
+    Taboc::new(file, 6)
+        .with_numbering(Some(numbering))
+        .with_list_style(list_style)
+        .parse()
+}
+
+#[test]
+fn test_decimal() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [1. Wire Protocol](#wire-protocol)
+  - [1.1 Introduction](#introduction)
+    - [1.1.1 Scope](#scope)
+    - [1.1.2 Terminology](#terminology)
+  - [1.2 Message Format](#message-format)
+    - [1.2.1 Header](#header)
+      - [1.2.1.1 Flags](#flags)
+    - [1.2.2 Payload](#payload)
+      - [1.2.2.1 Wire format](#wire-format)
+        - [1.2.2.1.1 Padding](#padding)
+  - [1.3 Security Considerations](#security-considerations)
+    - [1.3.1 Known attacks](#known-attacks)";
+
+    assert_eq!(expected, toc(Numbering::default(), ListStyle::default())?);
+
+    Ok(())
+}
+
+#[test]
+fn test_styles_and_start_level() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Wire Protocol](#wire-protocol)
+  - [I. Introduction](#introduction)
+    - [I.a Scope](#scope)
+    - [I.b Terminology](#terminology)
+  - [II. Message Format](#message-format)
+    - [II.a Header](#header)
+      - [II.a.1 Flags](#flags)
+    - [II.b Payload](#payload)
+      - [II.b.1 Wire format](#wire-format)
+        - [II.b.1.1 Padding](#padding)
+  - [III. Security Considerations](#security-considerations)
+    - [III.a Known attacks](#known-attacks)";
+
+    let numbering = Numbering {
+        styles: vec![
+            NumberStyle::UpperRoman,
+            NumberStyle::LowerAlpha,
+            NumberStyle::Decimal,
+        ],
+        start_level: 2,
+    };
+    assert_eq!(expected, toc(numbering, ListStyle::default())?);
+
+    Ok(())
+}
+
+#[test]
+fn test_flat() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Wire Protocol](#wire-protocol)
+- [1. Introduction](#introduction)
+- [1.1 Scope](#scope)
+- [1.2 Terminology](#terminology)
+- [2. Message Format](#message-format)
+- [2.1 Header](#header)
+- [2.1.1 Flags](#flags)
+- [2.2 Payload](#payload)
+- [2.2.1 Wire format](#wire-format)
+- [2.2.1.1 Padding](#padding)
+- [3. Security Considerations](#security-considerations)
+- [3.1 Known attacks](#known-attacks)";
+
+    let numbering = Numbering {
+        start_level: 2,
+        ..Numbering::default()
+    };
+    let list_style = ListStyle {
+        nested: false,
+        ..ListStyle::default()
+    };
+    assert_eq!(expected, toc(numbering, list_style)?);
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::prelude::*;
+
+fn selection_mock() -> Result<Taboc, Error> {
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/selection.md"))?;
+
+    Ok(Taboc::new(file, 3))
+}
+
+#[test]
+fn test_skip_title_and_min_depth() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Installation](#installation)
+  - [From source](#from-source)
+- [Usage](#usage)
+  - [Examples](#examples)
+- [Contributing](#contributing)
+  - [Code style](#code-style)
+- [License](#license)";
+
+    assert_eq!(expected, selection_mock()?.with_skip_title(true).parse()?);
+    assert_eq!(expected, selection_mock()?.with_min_depth(2).parse()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_include_and_exclude() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Project Title](#project-title)
+  - [Installation](#installation)
+    - [From source](#from-source)
+  - [Usage](#usage)
+    - [Examples](#examples)";
+
+    let taboc = selection_mock()?.with_exclude(vec!["license".parse()?, "regex:^Contrib".parse()?]);
+    assert_eq!(expected, taboc.parse()?);
+
+    let expected = "
+
+## Table of contents
+
+- [Examples](#examples)
+  - [Advanced Examples](#advanced-examples)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/selection.md"))?;
+    let taboc = Taboc::new(file, 6).with_include(vec!["*examples".parse()?]);
+    assert_eq!(expected, taboc.parse()?);
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use crate::prelude::*;
+
+#[test]
+fn test_setext_headings() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [Setext Title](#setext-title)
+  - [Heading Two](#heading-two)
+  - [A paragraph that spans multiple lines](#a-paragraph-that-spans-multiple-lines)
+  - [Heading Two Number 2](#heading-two-number-2)
+  - [Heading Two Number 3](#heading-two-number-3)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/setext.md"))?;
+
+    assert_eq!(expected, Taboc::new(file, 6).parse()?);
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use crate::prelude::*;
+
+#[test]
+fn test_skipped_levels() -> Result<(), Error> {
+    let expected = "
+
+## Table of contents
+
+- [No First-Level Heading](#no-first-level-heading)
+  - [Skipped a Level](#skipped-a-level)
+  - [Heading Three](#heading-three)
+- [Heading Two](#heading-two)";
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/skipped_levels.md"))?;
+
+    assert_eq!(expected, Taboc::new(file, 6).parse()?);
+
+    Ok(())
+}
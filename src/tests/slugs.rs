@@ -0,0 +1,336 @@
+use crate::{
+    prelude::*,
+    utils::{backend::Backend, slug::SlugStyle},
+};
+
+fn slugs(style: &str) -> Result<Vec<String>, Error> {
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/slugs.md"))?;
+
+    Taboc::new(file, 6)
+        .with_slugger(style.parse::<SlugStyle>()?)
+        .headings()?
+        .map(|heading| Ok(heading?.slug))
+        .collect()
+}
+
+#[test]
+fn test_github_slugs() -> Result<(), Error> {
+    let expected = [
+        "slug-styles",
+        "whats-new-in-v20",
+        "install----the-easy-way",
+        "c--rust",
+        "snake_case-names",
+        "2024",
+        "über-straße",
+        "party--time",
+        "whats-new-in-v20-1",
+        "3-usage",
+        "help",
+        "whats-new-in-v20-2",
+    ];
+
+    assert_eq!(expected.as_slice(), slugs("github")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_gitlab_slugs() -> Result<(), Error> {
+    let expected = [
+        "slug-styles",
+        "whats-new-in-v20",
+        "install-the-easy-way",
+        "c-rust",
+        "snake_case-names",
+        "anchor-2024",
+        "über-straße",
+        "party-time",
+        "whats-new-in-v20-1",
+        "3-usage",
+        "help",
+        "whats-new-in-v20-2",
+    ];
+
+    assert_eq!(expected.as_slice(), slugs("gitlab")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_rustdoc_slugs() -> Result<(), Error> {
+    let expected = [
+        "slug-styles",
+        "whats-new-in-v20",
+        "install----the-easy-way",
+        "c--rust",
+        "snake_case-names",
+        "2024",
+        "Über-straße",
+        "party--time",
+        "whats-new-in-v20-1",
+        "3-usage",
+        // Taken by the help popover of the rustdoc page.
+        "help-1",
+        "whats-new-in-v20-2",
+    ];
+
+    assert_eq!(expected.as_slice(), slugs("rustdoc")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_mdbook_slugs() -> Result<(), Error> {
+    let expected = [
+        "slug-styles",
+        "whats-new-in-v20",
+        "install----the-easy-way",
+        "c--rust",
+        "snake_case-names",
+        "2024",
+        "Über-straße",
+        "party--time",
+        "whats-new-in-v20-1",
+        "3-usage",
+        "help",
+        "whats-new-in-v20-2",
+    ];
+
+    assert_eq!(expected.as_slice(), slugs("mdbook")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_pandoc_slugs() -> Result<(), Error> {
+    let expected = [
+        "slug-styles",
+        "whats-new-in-v2.0",
+        "install----the-easy-way",
+        "c-rust",
+        "snake_case-names",
+        "section",
+        "über-straße",
+        "party-time",
+        "whats-new-in-v2.0-1",
+        "usage",
+        "help",
+        "whats-new-in-v2.0-2",
+    ];
+
+    assert_eq!(expected.as_slice(), slugs("pandoc")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_python_markdown_slugs() -> Result<(), Error> {
+    let expected = [
+        "slug-styles",
+        "whats-new-in-v20",
+        "install-the-easy-way",
+        "c-rust",
+        "snake_case-names",
+        "2024",
+        "uber-strae",
+        "party-time",
+        "whats-new-in-v20_1",
+        "3-usage",
+        "help",
+        "whats-new-in-v20_2",
+    ];
+
+    assert_eq!(expected.as_slice(), slugs("python-markdown")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_hugo_slugs() -> Result<(), Error> {
+    let expected = [
+        "slug-styles",
+        "whats-new-in-v20",
+        "install----the-easy-way",
+        "c--rust",
+        "snake_case-names",
+        "2024",
+        "über-straße",
+        "party--time",
+        "whats-new-in-v20-1",
+        "3-usage",
+        "help",
+        "whats-new-in-v20-2",
+    ];
+
+    assert_eq!(expected.as_slice(), slugs("hugo")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_jekyll_slugs() -> Result<(), Error> {
+    let expected = [
+        "slug-styles",
+        "whats-new-in-v20",
+        "install----the-easy-way",
+        "c--rust",
+        "snake_case-names",
+        "2024",
+        "über-straße",
+        "party--time",
+        "whats-new-in-v20-1",
+        "3-usage",
+        "help",
+        "whats-new-in-v20-2",
+    ];
+
+    assert_eq!(expected.as_slice(), slugs("jekyll")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_kramdown_slugs() -> Result<(), Error> {
+    let expected = [
+        "slug-styles",
+        "whats-new-in-v20",
+        "install----the-easy-way",
+        "c--rust",
+        "snakecase-names",
+        "section",
+        "ber-strae",
+        "party--time",
+        "whats-new-in-v20-1",
+        "usage",
+        "help",
+        "whats-new-in-v20-2",
+    ];
+
+    assert_eq!(expected.as_slice(), slugs("kramdown")?);
+
+    Ok(())
+}
+
+fn kramdown_slugs(style: SlugStyle, backend: Backend) -> Result<Vec<String>, Error> {
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/kramdown.md"))?;
+
+    Taboc::new(file, 6)
+        .with_slugger(style)
+        .with_backend(backend)
+        .headings()?
+        .map(|heading| Ok(heading?.slug))
+        .collect()
+}
+
+/// kramdown makes the ids from the markdown source of the headings.
+fn check_kramdown_raw_text(backend: Backend) -> Result<(), Error> {
+    let jekyll = [
+        "kramdown-ids",
+        "see-the-docshttpsdocsrs",
+        "use-tabocnew",
+        "custom",
+        "2-setup",
+    ];
+    let kramdown = [
+        "kramdown-ids",
+        "see-the-docshttpsdocsrs",
+        "use-tabocnew",
+        "custom",
+        "setup",
+    ];
+
+    assert_eq!(
+        jekyll.as_slice(),
+        kramdown_slugs(SlugStyle::Jekyll, backend)?
+    );
+    assert_eq!(
+        kramdown.as_slice(),
+        kramdown_slugs(SlugStyle::Kramdown, backend)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_kramdown_raw_text() -> Result<(), Error> {
+    check_kramdown_raw_text(Backend::Scanner)
+}
+
+#[test]
+#[cfg(feature = "commonmark")]
+fn test_kramdown_raw_text_commonmark() -> Result<(), Error> {
+    check_kramdown_raw_text(Backend::CommonMark)
+}
+
+#[test]
+fn test_docusaurus_slugs() -> Result<(), Error> {
+    let expected = [
+        "slug-styles",
+        "whats-new-in-v20",
+        "install----the-easy-way",
+        "c--rust",
+        "snake_case-names",
+        "2024",
+        "über-straße",
+        "party--time",
+        "whats-new-in-v20-1",
+        "3-usage",
+        "help",
+        "whats-new-in-v20-2",
+    ];
+
+    assert_eq!(expected.as_slice(), slugs("docusaurus")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_zola_slugs() -> Result<(), Error> {
+    let expected = [
+        "slug-styles",
+        "what-s-new-in-v2-0",
+        "install-the-easy-way",
+        "c-rust",
+        "snake-case-names",
+        "2024",
+        "uber-strasse",
+        "party-tada-time",
+        "what-s-new-in-v2-0-1",
+        "3-usage",
+        "help",
+        "what-s-new-in-v2-0-2",
+    ];
+
+    assert_eq!(expected.as_slice(), slugs("zola")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_slug_style() {
+    assert!("confluence".parse::<SlugStyle>().is_err());
+}
+
+#[test]
+fn test_python_markdown_unique() -> Result<(), Error> {
+    let expected = [
+        "python-markdown-ids",
+        "_1",
+        "_2",
+        "intro_1",
+        "intro_2",
+        "intro",
+        "intro_3",
+    ];
+
+    let file = std::fs::File::open(std::env::current_dir()?.join("mock_data/python_markdown.md"))?;
+    let slugs = Taboc::new(file, 6)
+        .with_slugger(SlugStyle::PythonMarkdown)
+        .headings()?
+        .map(|heading| Ok(heading?.slug))
+        .collect::<Result<Vec<_>, Error>>()?;
+    assert_eq!(expected.as_slice(), slugs);
+
+    Ok(())
+}
@@ -4,7 +4,18 @@ Contains the CLI Argument Parsing struct ([`Opt`]).
 Refer to the [`clap`] and [`clio`] crates.
 */
 
-use crate::prelude::*;
+use crate::{
+    prelude::*,
+    utils::{
+        backend::Backend,
+        decoding::Decoding,
+        flavor::{Extension, Flavor},
+        list_style::{Bullet, Indent},
+        numbering::NumberStyle,
+        pattern::Pattern,
+        slug::SlugStyle,
+    },
+};
 use clap::ArgAction;
 
 /// A table of contents generator for markdown documents written in Rust.
@@ -31,4 +42,81 @@ pub struct Opt {
     /// Max heading depth for the table of contents.
     #[clap(long, default_value = "6")]
     pub max_depth: usize,
+    /// Min heading depth for the table of contents.
+    #[clap(long, default_value = "1")]
+    pub min_depth: usize,
+    /// Don't include the first first-level heading (the title of the document).
+    #[clap(long, action=ArgAction::SetTrue)]
+    pub skip_title: bool,
+    /// Only include headings matching the pattern. Can be used multiple times. Patterns are
+    /// case-insensitive globs, or regular expressions if prefixed with `regex:`.
+    #[clap(long, value_name = "PATTERN")]
+    pub include: Vec<Pattern>,
+    /// Exclude headings (and their subsections) matching the pattern. Can be used multiple times.
+    /// Patterns are case-insensitive globs, or regular expressions if prefixed with `regex:`.
+    #[clap(long, value_name = "PATTERN")]
+    pub exclude: Vec<Pattern>,
+    /// Use the `title` of the front matter as a first-level heading if the document doesn't have
+    /// one.
+    #[clap(long, action=ArgAction::SetTrue)]
+    pub front_matter_title: bool,
+    /// The encoding of [INPUT]: `utf-8`, `lossy` (UTF-8 with invalid sequences replaced) or an
+    /// ASCII-compatible legacy encoding like `windows-1252`.
+    #[clap(long, value_name = "ENCODING", default_value = "utf-8")]
+    pub encoding: Decoding,
+    /// How the headings are found: `scanner` (the built-in line scanner) or `commonmark` (a
+    /// spec-compliant parser, requires the `commonmark` feature).
+    #[clap(long, value_name = "BACKEND", default_value = "scanner")]
+    pub backend: Backend,
+    /// The markdown flavor: `commonmark`, `gfm`, `pandoc` or `jekyll`. It decides which extension
+    /// blocks (that can't contain headings) are skipped.
+    #[clap(long, value_name = "FLAVOR", default_value = "gfm")]
+    pub flavor: Flavor,
+    /// Skip an extension block regardless of the flavor: `math`, `fenced-divs` or `liquid`. Can be
+    /// used multiple times.
+    #[clap(long, value_name = "EXTENSION")]
+    pub enable: Vec<Extension>,
+    /// Don't skip an extension block of the flavor: `math`, `fenced-divs` or `liquid`. Can be used
+    /// multiple times.
+    #[clap(long, value_name = "EXTENSION")]
+    pub disable: Vec<Extension>,
+    /// Treat [INPUT] as an MDX document (skip its ESM, JSX and expression blocks). It's the
+    /// default for `.mdx` files.
+    #[clap(long, action=ArgAction::SetTrue)]
+    pub mdx: bool,
+    /// How the heading anchors are made: `github`, `gitlab`, `rustdoc`, `mdbook`, `pandoc`,
+    /// `python-markdown` (MkDocs), `hugo`, `jekyll` (kramdown's `GFM` input), `kramdown`,
+    /// `docusaurus` or `zola`.
+    #[clap(long, value_name = "STYLE", default_value = "github")]
+    pub slug_style: SlugStyle,
+    /// Transliterate the heading text to ASCII before making the anchors (`Größe` -> `grosse`),
+    /// for platforms which don't support non-ASCII anchors.
+    #[clap(long, action=ArgAction::SetTrue)]
+    pub ascii_slugs: bool,
+    /// Insert explicit ids (`<a id="...">`) into the headings of the table of contents which
+    /// don't have one, so their anchors don't depend on the platform rendering [INPUT].
+    #[clap(long, action=ArgAction::SetTrue)]
+    pub insert_ids: bool,
+    /// The bullet of the table of contents entries: `-`, `*` or `+`.
+    #[clap(long, value_name = "BULLET", default_value = "-")]
+    pub bullet: Bullet,
+    /// Make the table of contents an ordered (`1.`, `2.`, ...) list.
+    #[clap(long, action=ArgAction::SetTrue)]
+    pub ordered: bool,
+    /// The indentation of the nested entries: a number of spaces or `tab`. Defaults to the width
+    /// of the list marker (2 spaces for bullets and 3 for `1.`).
+    #[clap(long, value_name = "WIDTH")]
+    pub indent: Option<Indent>,
+    /// Don't nest the subsections, make the table of contents a flat list.
+    #[clap(long, action=ArgAction::SetTrue)]
+    pub flat: bool,
+    /// Number the entries (`1.`, `1.1`, ...) with the comma-separated styles of each level:
+    /// `decimal`, `alpha`, `upper-alpha`, `roman` or `upper-roman`. The last one is used for the
+    /// deeper levels too.
+    #[clap(long, value_name = "STYLES", value_delimiter = ',')]
+    pub numbering: Vec<NumberStyle>,
+    /// The heading level the numbering starts at. The headings above it (like the title of the
+    /// document) aren't numbered.
+    #[clap(long, value_name = "LEVEL", default_value = "1")]
+    pub numbering_start: usize,
 }
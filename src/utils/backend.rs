@@ -0,0 +1,47 @@
+/*!
+The backends finding the headings of a document.
+
+The built-in line scanner covers the CommonMark block structure the headings depend on (code
+blocks, HTML blocks, setext headings, ...) without parsing the whole document. The `commonmark`
+feature adds a spec-compliant parser for documents relying on the rest of the spec.
+*/
+
+use crate::prelude::*;
+use std::str::FromStr;
+
+/// How the headings of a document are found.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// The built-in line scanner.
+    #[default]
+    Scanner,
+    /// A spec-compliant CommonMark parser with the GitHub Flavored Markdown extensions.
+    #[cfg(feature = "commonmark")]
+    CommonMark,
+}
+
+impl Backend {
+    const SCANNER: &'static str = "scanner";
+    const COMMONMARK: &'static str = "commonmark";
+}
+
+impl FromStr for Backend {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            Self::SCANNER => Ok(Self::Scanner),
+            #[cfg(feature = "commonmark")]
+            Self::COMMONMARK => Ok(Self::CommonMark),
+            #[cfg(not(feature = "commonmark"))]
+            Self::COMMONMARK => Err(anyhow!(
+                "The `commonmark` backend requires the `commonmark` feature."
+            )),
+            _ => Err(anyhow!(
+                "Unknown backend: `{s}`. Expected `{}` or `{}`.",
+                Self::SCANNER,
+                Self::COMMONMARK
+            )),
+        }
+    }
+}
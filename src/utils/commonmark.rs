@@ -0,0 +1,223 @@
+/*!
+Heading extraction with a spec-compliant CommonMark parser ([`pulldown_cmark`]), along with the
+GitHub Flavored Markdown extensions (tables, task lists, strikethrough and footnotes).
+
+Unlike the built-in line scanner, it finds the headings in list items and block quotes, and it
+handles lazy continuation lines, link reference definitions and the inline markdown of the
+headings exactly as the spec describes them. The headings are the same otherwise: they get the same
+anchors, line numbers, byte ranges and opt-out markers.
+*/
+
+use crate::utils::{
+    heading::Heading,
+    inline,
+    slug::Slugger,
+    toc::{Marker, Taboc},
+};
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use std::{collections::HashMap, ops::Range};
+
+/// A line of the document.
+struct Line {
+    /// The byte offset of the line in the document text.
+    start: usize,
+    /// The 1-based line number in the file.
+    number: usize,
+    /// The byte range of the line in the file, without the line ending.
+    range: Range<u64>,
+}
+
+/// The decoded text of a document (with `\n` line endings), along with where its lines are in the
+/// file.
+#[derive(Default)]
+pub struct Document {
+    text: String,
+    lines: Vec<Line>,
+}
+
+impl Document {
+    /// Add a line (without its line ending) to the document.
+    pub fn push_line(&mut self, line: &str, number: usize, range: Range<u64>) {
+        self.lines.push(Line {
+            start: self.text.len(),
+            number,
+            range,
+        });
+        self.text.push_str(line);
+        self.text.push('\n');
+    }
+
+    /// Get the line containing a byte offset of the document text.
+    fn line(&self, offset: usize) -> Option<&Line> {
+        let i = self.lines.partition_point(|l| l.start <= offset);
+        self.lines.get(i.checked_sub(1)?)
+    }
+
+    /// Get the byte range of the lines spanned by a range of the document text, without the last
+    /// line ending.
+    fn line_range(&self, range: &Range<usize>) -> Option<(usize, Range<u64>)> {
+        let end = self.text[..range.end].trim_end_matches('\n').len();
+        let first = self.line(range.start)?;
+        let last = self.line(end.saturating_sub(1).max(range.start))?;
+
+        Some((first.number, first.range.start..last.range.end))
+    }
+}
+
+/// A heading which is being parsed.
+struct PendingHeading {
+    level: usize,
+    id: Option<String>,
+    /// The range of the whole heading in the document text.
+    range: Range<usize>,
+    /// The range of the inline content of the heading in the document text.
+    content: Option<Range<usize>>,
+    text: String,
+    /// The text outside of the images.
+    anchor_text: String,
+    /// The image nesting depth.
+    images: usize,
+    omitted: bool,
+}
+
+impl PendingHeading {
+    fn push(&mut self, event: &Event<'_>, range: Range<usize>) {
+        let text = match event {
+            Event::Text(text)
+            | Event::Code(text)
+            | Event::InlineMath(text)
+            | Event::DisplayMath(text) => text,
+            Event::SoftBreak | Event::HardBreak => " ",
+            Event::Start(Tag::Image { .. }) => {
+                self.images += 1;
+                ""
+            }
+            Event::End(TagEnd::Image) => {
+                self.images = self.images.saturating_sub(1);
+                ""
+            }
+            _ => "",
+        };
+        self.text.push_str(text);
+        if self.images == 0 {
+            self.anchor_text.push_str(text);
+        }
+
+        self.content = Some(match self.content.take() {
+            Some(content) => content.start.min(range.start)..content.end.max(range.end),
+            None => range,
+        });
+    }
+
+    /// Get the heading text as it's written in the document. The attribute block of an ATX
+    /// heading is a part of it, like it is with the line scanner.
+    fn raw(&self, document: &Document) -> String {
+        let source = document.text[self.range.clone()].trim_start_matches([' ', '\t']);
+        match source.lines().next() {
+            Some(line) if line.starts_with('#') => Taboc::atx_text(self.level, line).to_owned(),
+            _ => self.content.clone().map_or_else(String::new, |content| {
+                document.text[content]
+                    .lines()
+                    .map(str::trim)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }),
+        }
+    }
+
+    /// Make the heading. Empty headings are skipped.
+    fn finish(
+        self,
+        document: &Document,
+        links: &mut HashMap<String, usize>,
+        slugger: &dyn Slugger,
+    ) -> Option<Heading> {
+        let raw = self.raw(document);
+        if raw.is_empty() {
+            return None;
+        }
+        let (line, range) = document.line_range(&self.range)?;
+
+        let text = self.text.trim().to_owned();
+        let (source, source_id) = inline::heading_id(&raw);
+        let id = self.id.or(source_id);
+        let slug = if slugger.slugs_raw_text() {
+            Taboc::link(links, slugger, source.trim(), id)
+        } else if slugger.includes_image_alt() {
+            Taboc::link(links, slugger, &text, id)
+        } else {
+            Taboc::link(links, slugger, &self.anchor_text, id)
+        };
+
+        Some(Heading {
+            level: self.level,
+            omitted: self.omitted || Marker::find(&raw) == Some(Marker::Ignore),
+            raw,
+            text,
+            slug,
+            line,
+            range,
+        })
+    }
+}
+
+/// Find the headings of a document. The `links` are the ones taken before its first heading.
+pub fn headings(
+    document: &Document,
+    slugger: &dyn Slugger,
+    mut links: HashMap<String, usize>,
+) -> Vec<Heading> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_HEADING_ATTRIBUTES;
+
+    let mut res = Vec::new();
+    let mut pending: Option<PendingHeading> = None;
+    // The line number of the last `omit in toc` marker. It omits the heading on the next line.
+    let mut marker_line = None;
+    let mut in_ignored_region = false;
+
+    for (event, range) in Parser::new_ext(&document.text, options).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, id, .. }) => {
+                let line = document.line(range.start).map(|l| l.number);
+                pending = Some(PendingHeading {
+                    level: level as usize,
+                    id: id.map(|id| id.to_string()),
+                    range,
+                    content: None,
+                    text: String::new(),
+                    anchor_text: String::new(),
+                    images: 0,
+                    omitted: in_ignored_region
+                        || marker_line.is_some_and(|m: usize| Some(m + 1) == line),
+                });
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some(heading) = pending.take() {
+                    res.extend(heading.finish(document, &mut links, slugger));
+                }
+            }
+            // Only standalone comments are markers (and the heading line itself).
+            Event::Html(html) if html.trim_start().starts_with(Marker::COMMENT_START) => {
+                match Marker::find(&html) {
+                    Some(Marker::Ignore) => {
+                        marker_line = document.line(range.start).map(|l| l.number);
+                    }
+                    Some(Marker::IgnoreStart) => in_ignored_region = true,
+                    Some(Marker::IgnoreEnd) => in_ignored_region = false,
+                    None => {}
+                }
+            }
+            event => {
+                if let Some(heading) = pending.as_mut() {
+                    heading.push(&event, range);
+                }
+            }
+        }
+    }
+
+    res
+}
@@ -0,0 +1,140 @@
+/*!
+Decoding of the document bytes.
+
+Markdown documents are expected to be UTF-8, but older ones can be written in a legacy encoding
+like Latin-1. Lines are decoded one at a time, so only ASCII-compatible encodings (where `\n` and
+`#` are always single bytes) are supported.
+
+```
+use taboc::utils::decoding::Decoding;
+
+fn main() -> Result<(), anyhow::Error> {
+    let latin1: Decoding = "latin1".parse()?;
+    assert_eq!(latin1.decode(b"Caf\xE9")?, "Café");
+
+    let lossy: Decoding = "lossy".parse()?;
+    assert_eq!(lossy.decode(b"Caf\xE9")?, "Caf\u{FFFD}");
+
+    assert!(Decoding::Strict.decode(b"Caf\xE9").is_err());
+
+    Ok(())
+}
+```
+*/
+
+use crate::prelude::*;
+use encoding_rs::{Encoding, UTF_8};
+use std::{borrow::Cow, str::FromStr, str::Utf8Error};
+
+/// How the bytes of a document are decoded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Decoding {
+    /// UTF-8. Invalid sequences are an error.
+    #[default]
+    Strict,
+    /// UTF-8. Invalid sequences are replaced with `U+FFFD`.
+    Lossy,
+    /// An ASCII-compatible legacy encoding. Malformed sequences are replaced with `U+FFFD`.
+    Encoding(&'static Encoding),
+}
+
+impl Decoding {
+    const LOSSY: &'static str = "lossy";
+
+    /// Decode a line of the document.
+    pub fn decode(self, bytes: &[u8]) -> Result<Cow<'_, str>, Utf8Error> {
+        match self {
+            Self::Strict => std::str::from_utf8(bytes).map(Cow::Borrowed),
+            Self::Lossy => Ok(String::from_utf8_lossy(bytes)),
+            Self::Encoding(encoding) => Ok(encoding.decode_without_bom_handling(bytes).0),
+        }
+    }
+
+    /// Find the length of the bytes at the start of a line which decode to `prefix`. It's not the
+    /// length of `prefix` when the line has invalid sequences (decoded to a 3 bytes `U+FFFD`) or
+    /// legacy characters.
+    pub fn source_len(self, bytes: &[u8], prefix: &str) -> Option<usize> {
+        match self {
+            Self::Strict => bytes.starts_with(prefix.as_bytes()).then_some(prefix.len()),
+            Self::Lossy | Self::Encoding(_) => (0..=bytes.len()).find(|&end| {
+                self.decode(&bytes[..end])
+                    .is_ok_and(|decoded| decoded == prefix)
+            }),
+        }
+    }
+
+    /// Encode text to be written in the document. Characters the encoding can't represent are
+    /// written as HTML numeric character references.
+    pub fn encode(self, text: &str) -> Cow<'_, [u8]> {
+        match self {
+            Self::Strict | Self::Lossy => Cow::Borrowed(text.as_bytes()),
+            Self::Encoding(encoding) => encoding.encode(text).0,
+        }
+    }
+}
+
+impl FromStr for Decoding {
+    type Err = Error;
+
+    /// Either `lossy` or a [WHATWG encoding label](https://encoding.spec.whatwg.org/#names-and-labels).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case(Self::LOSSY) {
+            return Ok(Self::Lossy);
+        }
+
+        let encoding =
+            Encoding::for_label(s.as_bytes()).ok_or_else(|| anyhow!("Unknown encoding: `{s}`."))?;
+        if encoding == UTF_8 {
+            Ok(Self::Strict)
+        } else if encoding.is_ascii_compatible() {
+            Ok(Self::Encoding(encoding))
+        } else {
+            Err(anyhow!(
+                "The `{}` encoding isn't supported, it isn't ASCII-compatible.",
+                encoding.name()
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Decoding;
+
+    #[test]
+    fn from_str() {
+        assert_eq!("utf-8".parse::<Decoding>().ok(), Some(Decoding::Strict));
+        assert_eq!("LOSSY".parse::<Decoding>().ok(), Some(Decoding::Lossy));
+        assert_eq!(
+            "windows-1252".parse::<Decoding>().ok(),
+            Some(Decoding::Encoding(encoding_rs::WINDOWS_1252))
+        );
+        assert!("utf-16le".parse::<Decoding>().is_err());
+        assert!("klingon".parse::<Decoding>().is_err());
+    }
+
+    #[test]
+    fn encode() {
+        let latin1 = Decoding::Encoding(encoding_rs::WINDOWS_1252);
+
+        assert_eq!(&*latin1.encode("Café"), b"Caf\xE9");
+        assert_eq!(&*latin1.encode("日"), b"&#26085;");
+        assert_eq!(&*Decoding::Lossy.encode("Café"), "Café".as_bytes());
+    }
+
+    #[test]
+    fn source_len() {
+        let latin1 = Decoding::Encoding(encoding_rs::WINDOWS_1252);
+
+        assert_eq!(
+            Decoding::Strict.source_len("## Café".as_bytes(), "## Caf"),
+            Some(6)
+        );
+        assert_eq!(
+            Decoding::Lossy.source_len(b"\xE9\xE9 # Caf", "\u{FFFD}\u{FFFD} # "),
+            Some(5)
+        );
+        assert_eq!(latin1.source_len(b"\xE9 # Caf", "é # "), Some(4));
+        assert_eq!(Decoding::Lossy.source_len(b"# Caf", "## "), None);
+    }
+}
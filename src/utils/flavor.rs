@@ -0,0 +1,111 @@
+/*!
+Markdown flavors and the extension blocks they add on top of CommonMark.
+
+Display math, Pandoc fenced divs and Liquid blocks regularly contain lines starting with `#`
+(LaTeX comments, shell snippets, ...) which aren't headings. Each flavor skips the extension blocks
+it knows about, and each of them can be toggled on its own.
+
+```
+use taboc::utils::flavor::{Extension, Flavor};
+
+fn main() -> Result<(), anyhow::Error> {
+    let mut extensions = "pandoc".parse::<Flavor>()?.extensions();
+    assert!(extensions.math && extensions.fenced_divs && !extensions.liquid);
+
+    extensions.set("fenced-divs".parse::<Extension>()?, false);
+    assert!(!extensions.fenced_divs);
+
+    Ok(())
+}
+```
+*/
+
+use crate::prelude::*;
+use std::str::FromStr;
+
+/// A markdown flavor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Flavor {
+    /// Plain CommonMark, without any extension blocks.
+    CommonMark,
+    /// GitHub Flavored Markdown: `$$` display math.
+    #[default]
+    Gfm,
+    /// Pandoc markdown: `$$` display math and `:::` fenced divs.
+    Pandoc,
+    /// Jekyll (kramdown with Liquid templates): `$$` display math and Liquid `{% raw %}`,
+    /// `{% highlight %}` and `{% comment %}` blocks.
+    Jekyll,
+}
+
+impl Flavor {
+    /// Get the extension blocks which are skipped in this flavor.
+    pub fn extensions(self) -> Extensions {
+        Extensions {
+            math: self != Self::CommonMark,
+            fenced_divs: self == Self::Pandoc,
+            liquid: self == Self::Jekyll,
+        }
+    }
+}
+
+impl FromStr for Flavor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "commonmark" => Ok(Self::CommonMark),
+            "gfm" | "github" => Ok(Self::Gfm),
+            "pandoc" => Ok(Self::Pandoc),
+            "jekyll" => Ok(Self::Jekyll),
+            _ => Err(anyhow!(
+                "Unknown flavor: `{s}`. Expected `commonmark`, `gfm`, `pandoc` or `jekyll`."
+            )),
+        }
+    }
+}
+
+/// An extension block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Extension {
+    /// `$$` display math.
+    Math,
+    /// Pandoc `:::` fenced divs.
+    FencedDivs,
+    /// Liquid `{% raw %}`, `{% highlight %}` and `{% comment %}` blocks.
+    Liquid,
+}
+
+impl FromStr for Extension {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "math" => Ok(Self::Math),
+            "fenced-divs" | "divs" => Ok(Self::FencedDivs),
+            "liquid" => Ok(Self::Liquid),
+            _ => Err(anyhow!(
+                "Unknown extension: `{s}`. Expected `math`, `fenced-divs` or `liquid`."
+            )),
+        }
+    }
+}
+
+/// The extension blocks which are skipped when looking for headings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Extensions {
+    pub math: bool,
+    pub fenced_divs: bool,
+    pub liquid: bool,
+}
+
+impl Extensions {
+    /// Enable or disable an extension block.
+    pub fn set(&mut self, extension: Extension, enabled: bool) {
+        match extension {
+            Extension::Math => self.math = enabled,
+            Extension::FencedDivs => self.fenced_divs = enabled,
+            Extension::Liquid => self.liquid = enabled,
+        }
+    }
+}
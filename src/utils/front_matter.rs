@@ -0,0 +1,172 @@
+/*!
+Front matter detection.
+
+Static site generators like Jekyll, Hugo, Zola and Docusaurus read metadata from a YAML (`---`) or
+TOML (`+++`) block at the very start of a markdown document. It isn't a part of the rendered
+document, so its lines shouldn't be scanned for headings.
+*/
+
+use crate::{prelude::*, utils::text_format::BOM};
+use std::io::BufRead;
+
+/// The metadata language of the front matter, based on its delimiters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrontMatterKind {
+    /// Delimited by `---`. Can also be closed with the YAML document end marker: `...`.
+    Yaml,
+    /// Delimited by `+++`.
+    Toml,
+}
+
+impl FrontMatterKind {
+    const YAML_DELIMITER: &'static str = "---";
+    const YAML_END: &'static str = "...";
+    const TOML_DELIMITER: &'static str = "+++";
+
+    /// Get the front matter kind from its opening delimiter line.
+    fn from_delimiter(line: &str) -> Option<Self> {
+        match line.trim_end() {
+            Self::YAML_DELIMITER => Some(Self::Yaml),
+            Self::TOML_DELIMITER => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    fn is_closed_by(self, line: &str) -> bool {
+        let line = line.trim_end();
+        match self {
+            Self::Yaml => line == Self::YAML_DELIMITER || line == Self::YAML_END,
+            Self::Toml => line == Self::TOML_DELIMITER,
+        }
+    }
+
+    /// Get the value of a top-level `title` key.
+    fn title(self, line: &str) -> Option<String> {
+        let rest = line.strip_prefix("title")?.trim_start();
+        let value = match self {
+            Self::Yaml => rest.strip_prefix(':')?,
+            Self::Toml => rest.strip_prefix('=')?,
+        }
+        .trim();
+
+        let unquoted = ['"', '\'']
+            .into_iter()
+            .find_map(|q| value.strip_prefix(q)?.strip_suffix(q))
+            .unwrap_or(value);
+
+        (!unquoted.is_empty()).then(|| unquoted.to_owned())
+    }
+}
+
+/// Front matter at the start of a markdown document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrontMatter {
+    pub kind: FrontMatterKind,
+    /// The line count, including both delimiters.
+    pub lines: usize,
+    /// The length in bytes, including both delimiters and their line endings.
+    pub len: u64,
+    /// The value of the top-level `title` key, if there's one.
+    pub title: Option<String>,
+}
+
+impl FrontMatter {
+    /// Read the front matter from the start of a document. Unclosed front matter isn't considered
+    /// front matter at all (a leading `---` is just a thematic break then).
+    ///
+    /// A byte order mark before the opening delimiter is a part of the front matter.
+    pub fn read<R: BufRead>(mut reader: R) -> Result<Option<Self>, Error> {
+        let mut line_buf = Vec::new();
+
+        let char_count = reader.read_until(b'\n', &mut line_buf)?;
+        let delimiter = line_buf.strip_prefix(BOM).unwrap_or(&line_buf);
+        let Some(kind) = FrontMatterKind::from_delimiter(&String::from_utf8_lossy(delimiter))
+        else {
+            return Ok(None);
+        };
+
+        let mut front_matter = Self {
+            kind,
+            lines: 1,
+            len: char_count as u64,
+            title: None,
+        };
+        // TOML tables start a new scope, the title has to be before any of them.
+        let mut top_level = true;
+
+        loop {
+            line_buf.clear();
+            let char_count = reader.read_until(b'\n', &mut line_buf)?;
+            if char_count == 0 {
+                return Ok(None);
+            }
+
+            front_matter.lines += 1;
+            front_matter.len += char_count as u64;
+
+            let line = String::from_utf8_lossy(&line_buf);
+            if kind.is_closed_by(&line) {
+                return Ok(Some(front_matter));
+            }
+
+            top_level &= !(kind == FrontMatterKind::Toml && line.starts_with('['));
+            if top_level && front_matter.title.is_none() {
+                front_matter.title = kind.title(&line);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrontMatter, FrontMatterKind};
+
+    #[test]
+    fn title() {
+        let yaml = FrontMatterKind::Yaml;
+        let toml = FrontMatterKind::Toml;
+
+        assert_eq!(yaml.title("title: Hello"), Some("Hello".to_owned()));
+        assert_eq!(
+            yaml.title("title: \"Hello: World\""),
+            Some("Hello: World".to_owned())
+        );
+        assert_eq!(yaml.title("title:"), None);
+        assert_eq!(yaml.title("  title: Nested"), None);
+        assert_eq!(yaml.title("titles: Hello"), None);
+        assert_eq!(toml.title("title = 'Hello'"), Some("Hello".to_owned()));
+        assert_eq!(toml.title("title: Hello"), None);
+    }
+
+    #[test]
+    fn unclosed() -> Result<(), anyhow::Error> {
+        assert_eq!(FrontMatter::read("---\ntitle: Hello\n".as_bytes())?, None);
+        assert_eq!(FrontMatter::read("# Hello\n---\n".as_bytes())?, None);
+        assert_eq!(
+            FrontMatter::read("+++\n[extra]\ntitle = \"Nested\"\n+++\n# Hello".as_bytes())?,
+            Some(FrontMatter {
+                kind: FrontMatterKind::Toml,
+                lines: 4,
+                len: 33,
+                title: None,
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn bom_and_crlf() -> Result<(), anyhow::Error> {
+        assert_eq!(
+            FrontMatter::read("\u{feff}---\r\ntitle: Hello\r\n---\r\n# Hello".as_bytes())?,
+            Some(FrontMatter {
+                kind: FrontMatterKind::Yaml,
+                lines: 3,
+                len: 27,
+                title: Some("Hello".to_owned()),
+            })
+        );
+
+        Ok(())
+    }
+}
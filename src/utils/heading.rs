@@ -0,0 +1,156 @@
+/*!
+The heading model. Headings are what a table of contents is made of, but they can also be used to
+make custom renderers, linters or editor features.
+
+```
+use taboc::prelude::*;
+
+fn main() -> Result<(), Error> {
+    let taboc = Taboc::new(std::fs::File::open("mock_data/README.md")?, 6);
+
+    for heading in taboc.headings()? {
+        let heading = heading?;
+        println!("{}:{} #{}", heading.line, heading.level, heading.slug);
+    }
+
+    Ok(())
+}
+```
+*/
+
+use std::ops::Range;
+
+/// A markdown heading, either an ATX (`## Heading`) or a setext one (underlined with `===` or
+/// `---`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Heading {
+    /// The heading level, from 1 to 6.
+    pub level: usize,
+    /// The heading text as it's written in the document, without the `#` sequences or the setext
+    /// underline.
+    pub raw: String,
+    /// The plain text of the heading, without any inline markdown.
+    pub text: String,
+    /// The anchor of the heading. It's unique among the headings of the document.
+    pub slug: String,
+    /// The 1-based line number of the heading. Multi-line setext headings start on this line.
+    pub line: usize,
+    /// The byte range of the heading in the file, without the line ending.
+    pub range: Range<u64>,
+    /// The heading is opted out of the table of contents with a marker comment:
+    /// `<!-- omit in toc -->`, `<!-- taboc:ignore -->` or a `<!-- taboc:ignore-start -->` and
+    /// `<!-- taboc:ignore-end -->` region.
+    pub omitted: bool,
+}
+
+/// A table of contents entry along with its subsections.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TocNode {
+    pub heading: Heading,
+    pub children: Vec<TocNode>,
+}
+
+impl TocNode {
+    /// Build a tree out of the headings based on their levels.
+    ///
+    /// The headings nest under the closest preceding heading of a lower level, so skipped levels
+    /// are collapsed (a level 4 heading right after a level 2 one is its direct child). Headings
+    /// without such a parent are at the root of the tree, regardless of their level.
+    pub fn build(headings: impl IntoIterator<Item = Heading>) -> Vec<Self> {
+        let mut roots = Vec::new();
+        // The path from the root to the last heading.
+        let mut stack: Vec<Self> = Vec::new();
+
+        let close = |stack: &mut Vec<Self>, roots: &mut Vec<Self>| {
+            if let Some(node) = stack.pop() {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => roots.push(node),
+                }
+            }
+        };
+
+        for heading in headings {
+            while stack
+                .last()
+                .is_some_and(|node| node.heading.level >= heading.level)
+            {
+                close(&mut stack, &mut roots);
+            }
+            stack.push(Self {
+                heading,
+                children: Vec::new(),
+            });
+        }
+        while !stack.is_empty() {
+            close(&mut stack, &mut roots);
+        }
+
+        roots
+    }
+
+    /// Flatten the tree into its nodes (without their children), in document order.
+    pub fn flatten(nodes: Vec<Self>) -> Vec<Self> {
+        let mut res = Vec::new();
+        for mut node in nodes {
+            let children = std::mem::take(&mut node.children);
+            res.push(node);
+            res.extend(Self::flatten(children));
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Heading, TocNode};
+
+    fn heading(level: usize, text: &str) -> Heading {
+        Heading {
+            level,
+            raw: text.to_owned(),
+            text: text.to_owned(),
+            slug: text.to_lowercase(),
+            line: 1,
+            range: 0..0,
+            omitted: false,
+        }
+    }
+
+    /// Flatten the tree into the `(depth, text)` of each node.
+    fn flatten(nodes: &[TocNode], depth: usize, res: &mut Vec<(usize, String)>) {
+        for node in nodes {
+            res.push((depth, node.heading.text.clone()));
+            flatten(&node.children, depth + 1, res);
+        }
+    }
+
+    #[test]
+    fn build() {
+        let headings = [
+            (3, "A"),
+            (2, "B"),
+            (4, "C"),
+            (3, "D"),
+            (6, "E"),
+            (2, "F"),
+            (1, "G"),
+        ]
+        .map(|(level, text)| heading(level, text));
+        let mut res = Vec::new();
+        flatten(&TocNode::build(headings), 0, &mut res);
+
+        let expected = [
+            (0, "A"),
+            (0, "B"),
+            (1, "C"),
+            (1, "D"),
+            (2, "E"),
+            (0, "F"),
+            (0, "G"),
+        ]
+        .map(|(depth, text)| (depth, text.to_owned()));
+        assert_eq!(res, expected);
+    }
+}
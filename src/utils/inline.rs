@@ -0,0 +1,557 @@
+/*!
+Inline markdown handling for heading text.
+
+Headings can contain emphasis, code spans, links, images and inline HTML. The table of contents
+links shouldn't contain any of that markup, both in their text and in their anchors:
+
+```
+use taboc::utils::inline::{escape_link_text, plain_text};
+
+let text = plain_text("The `Taboc::parse` **function** [docs](https://docs.rs/taboc)");
+assert_eq!(text, "The Taboc::parse function docs");
+
+assert_eq!(escape_link_text("[a] \\ b"), "\\[a\\] \\\\ b");
+```
+
+Refer to the [CommonMark spec](https://spec.commonmark.org/0.31.2/#inlines).
+*/
+
+/// A piece of inline content. Delimiter runs (`*`, `_` and `~`) are resolved after the whole text
+/// is tokenized because they can only be matched with a later run.
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Delimiter {
+        marker: char,
+        len: usize,
+        can_open: bool,
+        can_close: bool,
+    },
+}
+
+impl Token {
+    fn text(&mut self) -> Option<&mut String> {
+        match self {
+            Self::Text(text) => Some(text),
+            Self::Delimiter { .. } => None,
+        }
+    }
+}
+
+/// Unicode punctuation (and symbols) as far as delimiter flanking is concerned.
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation() || !(c.is_alphanumeric() || c.is_whitespace() || c.is_control())
+}
+
+/// Get the length of the code span starting with a backtick run at the start of `s`, along with its
+/// content. Backtick runs without a closing run of the same length are literal text.
+fn code_span(s: &str) -> Option<(usize, String)> {
+    let ticks = s.chars().take_while(|c| *c == '`').count();
+    let mut pos = ticks;
+
+    while let Some(offset) = s[pos..].find('`') {
+        let start = pos + offset;
+        let len = s[start..].chars().take_while(|c| *c == '`').count();
+        if len == ticks {
+            let content = s[ticks..start].replace('\n', " ");
+            // A single leading and trailing space is stripped (so code spans can start with a
+            // backtick), unless the code span consists of only spaces.
+            let content = match content.strip_prefix(' ').and_then(|c| c.strip_suffix(' ')) {
+                Some(stripped) if !content.trim_matches(' ').is_empty() => stripped.to_owned(),
+                _ => content,
+            };
+            return Some((start + len, content));
+        }
+        pos = start + len;
+    }
+
+    None
+}
+
+/// Get the position of the `]` matching the `[` at the start of `s`. Brackets in code spans and
+/// escaped brackets don't count.
+fn closing_bracket(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut chars = s.char_indices();
+
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '`' => {
+                if let Some((len, _)) = code_span(&s[idx..]) {
+                    // Skip to the end of the code span.
+                    while chars.offset() < idx + len {
+                        chars.next();
+                    }
+                }
+            }
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Get the length of an inline link destination (and title) in parentheses at the start of `s`,
+/// or the length of a reference label in brackets.
+fn link_target(s: &str) -> Option<usize> {
+    if s.starts_with('[') {
+        return closing_bracket(s).map(|end| end + 1);
+    }
+    if !s.starts_with('(') {
+        return None;
+    }
+
+    let mut depth = 0usize;
+    let mut chars = s.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Get the length of an autolink or an inline HTML tag at the start of `s`. Autolinks keep their
+/// text while HTML tags are dropped entirely.
+fn angle_bracket(s: &str) -> Option<(usize, Option<&str>)> {
+    if let Some(comment) = s.strip_prefix("<!--") {
+        return comment.find("-->").map(|end| (end + "<!---->".len(), None));
+    }
+
+    let end = s.find('>')?;
+    let inner = &s[1..end];
+    if inner.is_empty() || inner.contains(['<', '\n']) {
+        return None;
+    }
+
+    let is_autolink = match inner.split_once(':') {
+        Some((scheme, _)) => {
+            (2..=32).contains(&scheme.len())
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '.' | '-'))
+                && !inner.contains(' ')
+        }
+        None => inner.contains('@') && !inner.contains(' '),
+    };
+    if is_autolink {
+        return Some((end + 1, Some(inner)));
+    }
+
+    let tag = inner.strip_prefix('/').unwrap_or(inner);
+    tag.starts_with(|c: char| c.is_ascii_alphabetic())
+        .then_some((end + 1, None))
+}
+
+/// Decode an HTML entity at the start of `s` (`&amp;`, `&#35;`, `&#x23;`, ...).
+fn entity(s: &str) -> Option<(usize, char)> {
+    let end = s.find(';')?;
+    let name = &s[1..end];
+    let c = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code).filter(|c| *c != '\0')?
+        }
+    };
+
+    Some((end + 1, c))
+}
+
+/// Append text to the last text token.
+fn push_text(tokens: &mut Vec<Token>, text: &str) {
+    match tokens.last_mut().and_then(Token::text) {
+        Some(last) => last.push_str(text),
+        None => tokens.push(Token::Text(text.to_owned())),
+    }
+}
+
+/// Split the inline markdown into text and emphasis delimiter runs. Images are left out unless
+/// `images` is set.
+fn tokenize(s: &str, images: bool) -> Vec<Token> {
+    let mut tokens = Vec::new();
+
+    let mut idx = 0;
+    while let Some(c) = s[idx..].chars().next() {
+        let rest = &s[idx..];
+
+        match c {
+            '\\' => {
+                if let Some(escaped) = rest[1..].chars().next().filter(char::is_ascii_punctuation) {
+                    push_text(&mut tokens, escaped.encode_utf8(&mut [0; 4]));
+                    idx += 1 + escaped.len_utf8();
+                    continue;
+                }
+            }
+            '`' => {
+                if let Some((len, content)) = code_span(rest) {
+                    push_text(&mut tokens, &content);
+                    idx += len;
+                } else {
+                    let ticks = rest.chars().take_while(|c| *c == '`').count();
+                    push_text(&mut tokens, &rest[..ticks]);
+                    idx += ticks;
+                }
+                continue;
+            }
+            '!' | '[' => {
+                let label_start = usize::from(c == '!');
+                if let Some(label_end) = rest[label_start..]
+                    .starts_with('[')
+                    .then(|| closing_bracket(&rest[label_start..]))
+                    .flatten()
+                {
+                    let label_end = label_start + label_end;
+                    if let Some(target_len) = link_target(&rest[label_end + 1..]) {
+                        if c == '[' || images {
+                            let label = &rest[label_start + 1..label_end];
+                            push_text(&mut tokens, &render(label, images));
+                        }
+                        idx += label_end + 1 + target_len;
+                        continue;
+                    }
+                }
+            }
+            '<' => {
+                if let Some((len, text)) = angle_bracket(rest) {
+                    push_text(&mut tokens, text.unwrap_or_default());
+                    idx += len;
+                    continue;
+                }
+            }
+            '&' => {
+                if let Some((len, decoded)) = entity(rest) {
+                    push_text(&mut tokens, decoded.encode_utf8(&mut [0; 4]));
+                    idx += len;
+                    continue;
+                }
+            }
+            '*' | '_' | '~' => {
+                let len = rest.chars().take_while(|m| *m == c).count();
+                let before = s[..idx].chars().next_back().unwrap_or(' ');
+                let after = rest[len..].chars().next().unwrap_or(' ');
+
+                let left_flanking = !after.is_whitespace()
+                    && (!is_punctuation(after) || before.is_whitespace() || is_punctuation(before));
+                let right_flanking = !before.is_whitespace()
+                    && (!is_punctuation(before) || after.is_whitespace() || is_punctuation(after));
+
+                let (can_open, can_close) = match c {
+                    // Intraword underscores (like in `snake_case`) aren't emphasis.
+                    '_' => (
+                        left_flanking && (!right_flanking || is_punctuation(before)),
+                        right_flanking && (!left_flanking || is_punctuation(after)),
+                    ),
+                    // GFM strikethrough only uses one or two tildes.
+                    '~' if len > 2 => (false, false),
+                    _ => (left_flanking, right_flanking),
+                };
+
+                tokens.push(Token::Delimiter {
+                    marker: c,
+                    len,
+                    can_open,
+                    can_close,
+                });
+                idx += len;
+                continue;
+            }
+            _ => {}
+        }
+
+        push_text(&mut tokens, &rest[..c.len_utf8()]);
+        idx += c.len_utf8();
+    }
+
+    tokens
+}
+
+/// Match the emphasis delimiter runs with each other. The matched delimiters are removed, whilst
+/// the unmatched ones stay as literal text.
+fn resolve_emphasis(tokens: &mut [Token]) {
+    let mut closer = 0;
+
+    while closer < tokens.len() {
+        let Token::Delimiter {
+            marker,
+            len: closer_len,
+            can_open: closer_can_open,
+            can_close: true,
+        } = tokens[closer]
+        else {
+            closer += 1;
+            continue;
+        };
+        if closer_len == 0 {
+            closer += 1;
+            continue;
+        }
+
+        let opener = (0..closer).rev().find(|&i| match tokens[i] {
+            Token::Delimiter {
+                marker: m,
+                len,
+                can_open: true,
+                can_close,
+            } if m == marker && len > 0 => {
+                if marker == '~' {
+                    return len == closer_len;
+                }
+                // The "rule of 3" for runs that can both open and close.
+                let both = can_close || closer_can_open;
+                !both || (len + closer_len) % 3 != 0 || (len % 3 == 0 && closer_len % 3 == 0)
+            }
+            _ => false,
+        });
+
+        let Some(opener) = opener else {
+            closer += 1;
+            continue;
+        };
+
+        let Token::Delimiter {
+            len: opener_len, ..
+        } = tokens[opener]
+        else {
+            continue;
+        };
+        let used = if marker == '~' {
+            closer_len
+        } else if opener_len >= 2 && closer_len >= 2 {
+            2
+        } else {
+            1
+        };
+
+        for (i, token) in tokens.iter_mut().enumerate().take(closer + 1).skip(opener) {
+            if let Token::Delimiter {
+                len,
+                can_open,
+                can_close,
+                ..
+            } = token
+            {
+                if i == opener || i == closer {
+                    *len -= used;
+                } else {
+                    // Delimiters between a matched pair can't match anything outside of it.
+                    *can_open = false;
+                    *can_close = false;
+                }
+            }
+        }
+    }
+}
+
+/// Render the inline markdown of a heading as plain text.
+///
+/// Emphasis is stripped, links and images are unwrapped to their text, code spans keep their
+/// contents and inline HTML tags are dropped.
+pub fn plain_text(markdown: &str) -> String {
+    render(markdown, true)
+}
+
+/// Render the inline markdown of a heading as plain text without the alt text of its images. It's
+/// what's left in the rendered HTML heading, which some platforms make the anchors from.
+pub fn anchor_text(markdown: &str) -> String {
+    render(markdown, false)
+}
+
+fn render(markdown: &str, images: bool) -> String {
+    let mut tokens = tokenize(markdown, images);
+    resolve_emphasis(&mut tokens);
+
+    let mut res = String::with_capacity(markdown.len());
+    for token in tokens {
+        match token {
+            Token::Text(text) => res.push_str(&text),
+            Token::Delimiter { marker, len, .. } => {
+                res.extend(std::iter::repeat_n(marker, len));
+            }
+        }
+    }
+
+    res
+}
+
+/// Get the value of an `id` (or `name`) attribute of an inline `<a>` tag.
+fn anchor_id(markdown: &str) -> Option<String> {
+    markdown.match_indices('<').find_map(|(idx, _)| {
+        let (len, None) = angle_bracket(&markdown[idx..])? else {
+            return None;
+        };
+        let tag = markdown[idx + 1..idx + len - 1].trim_end_matches('/');
+        let attributes = tag.strip_prefix(['a', 'A'])?;
+        if !attributes.starts_with([' ', '\t']) {
+            return None;
+        }
+
+        attributes.split_whitespace().find_map(|attribute| {
+            let value = attribute
+                .strip_prefix("id=")
+                .or_else(|| attribute.strip_prefix("name="))?
+                .trim_matches(['"', '\'']);
+            (!value.is_empty()).then(|| value.to_owned())
+        })
+    })
+}
+
+/// Split a trailing `{#custom-id .class key=value}` attribute block (kramdown, pandoc, ...) off of
+/// the heading text. Curly braces that don't only contain attributes are a part of the text.
+fn attribute_block(markdown: &str) -> Option<(&str, Option<&str>)> {
+    let trimmed = markdown.trim_end();
+    let start = trimmed.strip_suffix('}')?.rfind('{')?;
+    let block = &trimmed[start + 1..trimmed.len() - 1];
+    let attributes = block.strip_prefix(':').unwrap_or(block).split_whitespace();
+
+    let mut id = None;
+    for attribute in attributes {
+        match attribute.chars().next() {
+            Some('#') if attribute.len() > 1 => id = Some(&attribute[1..]),
+            Some('.') if attribute.len() > 1 => {}
+            // The pandoc shorthand for `.unnumbered`.
+            Some('-') if attribute.len() == 1 => {}
+            _ if attribute.contains('=') && !attribute.starts_with('=') => {}
+            _ => return None,
+        }
+    }
+
+    Some((&markdown[..start], id))
+}
+
+/// Get the explicit id of a heading along with the heading text without the attribute block.
+///
+/// The id is either in a trailing attribute block (`## Setup {#setup}`) or in an inline anchor
+/// (`## <a id="cfg"></a> Configuration`). The attribute block takes precedence.
+pub fn heading_id(markdown: &str) -> (&str, Option<String>) {
+    let (text, id) = attribute_block(markdown).unwrap_or((markdown, None));
+
+    match id {
+        Some(id) => (text, Some(id.to_owned())),
+        None => (text, anchor_id(text)),
+    }
+}
+
+/// The characters which would break the markdown link syntax of a table of contents entry or be
+/// read as inline markdown again (emphasis, code spans, HTML tags and entities).
+const LINK_TEXT_ESCAPED: [char; 10] = ['[', ']', '\\', '<', '>', '&', '*', '_', '`', '~'];
+
+/// Escape the link text of a table of contents entry, so it shows the plain text of the heading.
+pub fn escape_link_text(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        if LINK_TEXT_ESCAPED.contains(&c) {
+            res.push('\\');
+        }
+        res.push(c);
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{anchor_text, heading_id, plain_text};
+
+    #[test]
+    fn emphasis() {
+        assert_eq!(plain_text("**Bold** move"), "Bold move");
+        assert_eq!(plain_text("*a* _b_ ***c*** ~~d~~"), "a b c d");
+        assert_eq!(plain_text("snake_case_name"), "snake_case_name");
+        assert_eq!(plain_text("2 * 3 * 4"), "2 * 3 * 4");
+        assert_eq!(plain_text("**unclosed"), "**unclosed");
+        assert_eq!(plain_text("\\*escaped\\*"), "*escaped*");
+    }
+
+    #[test]
+    fn code_spans() {
+        assert_eq!(
+            plain_text("The `Taboc::parse` function"),
+            "The Taboc::parse function"
+        );
+        assert_eq!(plain_text("`` a`b ``"), "a`b");
+        assert_eq!(plain_text("`*not emphasis*`"), "*not emphasis*");
+        assert_eq!(plain_text("`unclosed"), "`unclosed");
+    }
+
+    #[test]
+    fn links() {
+        assert_eq!(plain_text("See [docs](https://x)"), "See docs");
+        assert_eq!(plain_text("See [the `docs`][ref]"), "See the docs");
+        assert_eq!(plain_text("Logo ![img](a.png)"), "Logo img");
+        assert_eq!(plain_text("[![badge](a.svg)](https://x)"), "badge");
+        assert_eq!(anchor_text("Logo ![img](a.png)"), "Logo ");
+        assert_eq!(anchor_text("[![badge](a.svg) CI](https://x)"), " CI");
+        assert_eq!(plain_text("[a (b)](https://x/(y))"), "a (b)");
+        assert_eq!(plain_text("[not a link]"), "[not a link]");
+        assert_eq!(plain_text("<https://x.com>"), "https://x.com");
+    }
+
+    #[test]
+    fn html() {
+        assert_eq!(plain_text("Footnote<sup>1</sup>"), "Footnote1");
+        assert_eq!(plain_text("Press <kbd>Ctrl</kbd>"), "Press Ctrl");
+        assert_eq!(plain_text("Hidden <!-- comment -->text"), "Hidden text");
+        assert_eq!(plain_text("a < b > c"), "a < b > c");
+        assert_eq!(plain_text("Fish &amp; chips &#35;1"), "Fish & chips #1");
+    }
+
+    #[test]
+    fn explicit_ids() {
+        assert_eq!(
+            heading_id("Setup {#setup}"),
+            ("Setup ", Some("setup".to_owned()))
+        );
+        assert_eq!(
+            heading_id("Setup {: #setup .class key=value}"),
+            ("Setup ", Some("setup".to_owned()))
+        );
+        assert_eq!(heading_id("Unnumbered {-}"), ("Unnumbered ", None));
+        assert_eq!(heading_id("Set {x}"), ("Set {x}", None));
+        assert_eq!(heading_id("Set {}"), ("Set ", None));
+        assert_eq!(
+            heading_id("<a id=\"cfg\"></a> Configuration"),
+            ("<a id=\"cfg\"></a> Configuration", Some("cfg".to_owned()))
+        );
+        assert_eq!(
+            heading_id("Old <a name='old'/>"),
+            ("Old <a name='old'/>", Some("old".to_owned()))
+        );
+        assert_eq!(
+            heading_id("<abbr id=\"x\">A</abbr>"),
+            ("<abbr id=\"x\">A</abbr>", None)
+        );
+    }
+}
@@ -0,0 +1,157 @@
+/*!
+The list style of the table of contents: the bullet, ordered (`1.`) entries, the indentation of the
+subsections and whether they're nested at all.
+
+```
+use taboc::utils::list_style::{Indent, ListStyle};
+
+fn main() -> Result<(), anyhow::Error> {
+    let style = ListStyle {
+        bullet: "*".parse()?,
+        indent: Some(Indent::Spaces(4)),
+        ..ListStyle::default()
+    };
+    assert_eq!(style.marker(0), "*");
+    assert_eq!(style.indent("*"), "    ");
+
+    let ordered = ListStyle {
+        ordered: true,
+        ..ListStyle::default()
+    };
+    assert_eq!(ordered.marker(9), "10.");
+    assert_eq!(ordered.indent("10."), "    ");
+
+    Ok(())
+}
+```
+*/
+
+use crate::prelude::*;
+use std::str::FromStr;
+
+/// The bullet of an unordered list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Bullet {
+    /// `-`
+    #[default]
+    Dash,
+    /// `*`
+    Asterisk,
+    /// `+`
+    Plus,
+}
+
+impl Bullet {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Dash => "-",
+            Self::Asterisk => "*",
+            Self::Plus => "+",
+        }
+    }
+}
+
+impl FromStr for Bullet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "-" | "dash" => Ok(Self::Dash),
+            "*" | "asterisk" => Ok(Self::Asterisk),
+            "+" | "plus" => Ok(Self::Plus),
+            _ => Err(anyhow!("Unknown bullet: `{s}`. Expected `-`, `*` or `+`.")),
+        }
+    }
+}
+
+/// The indentation of the nested entries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Indent {
+    /// A number of spaces per level.
+    Spaces(usize),
+    /// A tab per level.
+    Tab,
+}
+
+impl FromStr for Indent {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("tab") {
+            return Ok(Self::Tab);
+        }
+
+        match s.parse::<usize>() {
+            Ok(0) => Err(anyhow!("The indent width has to be at least 1.")),
+            Ok(width) => Ok(Self::Spaces(width)),
+            Err(_) => Err(anyhow!(
+                "Invalid indent: `{s}`. Expected a number of spaces or `tab`."
+            )),
+        }
+    }
+}
+
+/// How the table of contents list is rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ListStyle {
+    /// The bullet of the entries, unless they're ordered.
+    pub bullet: Bullet,
+    /// Number the entries (`1.`, `2.`, ...) of each list.
+    pub ordered: bool,
+    /// The indentation of the subsections. Defaults to the width of the marker of their parent
+    /// entry (2 spaces for bullets, 3 for `1.`), which is the least that nests them.
+    ///
+    /// NOTE: CommonMark doesn't nest the subsections of ordered entries with less than 3 spaces.
+    pub indent: Option<Indent>,
+    /// Nest the subsections under their parent entry. All the entries are at the top level of the
+    /// list otherwise.
+    pub nested: bool,
+}
+
+impl Default for ListStyle {
+    fn default() -> Self {
+        Self {
+            bullet: Bullet::default(),
+            ordered: false,
+            indent: None,
+            nested: true,
+        }
+    }
+}
+
+impl ListStyle {
+    /// Get the list marker of the `index`-th (0-based) entry of a list.
+    pub fn marker(&self, index: usize) -> String {
+        if self.ordered {
+            format!("{}.", index + 1)
+        } else {
+            self.bullet.as_str().to_owned()
+        }
+    }
+
+    /// Get the indentation of the subsections of an entry with a `marker`.
+    pub fn indent(&self, marker: &str) -> String {
+        match self.indent {
+            Some(Indent::Spaces(width)) => " ".repeat(width),
+            Some(Indent::Tab) => "\t".to_owned(),
+            None => " ".repeat(marker.len() + 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bullet, Indent};
+
+    #[test]
+    fn parse() {
+        assert_eq!("*".parse::<Bullet>().ok(), Some(Bullet::Asterisk));
+        assert_eq!("plus".parse::<Bullet>().ok(), Some(Bullet::Plus));
+        assert!("1.".parse::<Bullet>().is_err());
+
+        assert_eq!("4".parse::<Indent>().ok(), Some(Indent::Spaces(4)));
+        assert_eq!("TAB".parse::<Indent>().ok(), Some(Indent::Tab));
+        assert!("0".parse::<Indent>().is_err());
+        assert!("wide".parse::<Indent>().is_err());
+    }
+}
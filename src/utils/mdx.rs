@@ -0,0 +1,199 @@
+/*!
+MDX support.
+
+MDX documents (used by Docusaurus and friends) mix markdown with ESM `import`/`export`
+statements, JSX and `{...}` JavaScript expressions. Lines starting with `#` in them (comments in
+template literals, code passed as props, `{/* ... */}` comments, ...) aren't headings.
+
+The markdown children of JSX components are still markdown, only the tags and the expressions are
+skipped:
+
+```mdx
+import Tabs from '@theme/Tabs';
+
+<Tabs
+  # Not a heading, it's in the opening tag.
+  defaultValue="npm"
+>
+
+## A heading in a tab
+
+</Tabs>
+```
+*/
+
+use crate::prelude::*;
+use std::path::Path;
+
+/// Skips the ESM, JSX and expression blocks of an MDX document line by line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MdxBlocks {
+    /// An `import`/`export` block. It lasts until a blank line outside of its braces, strings and
+    /// comments.
+    esm: Option<Lexer>,
+    jsx: Lexer,
+}
+
+impl MdxBlocks {
+    const ESM_KEYWORDS: [&'static str; 2] = ["import", "export"];
+
+    /// Check if a path is an MDX document, based on its extension.
+    pub fn is_mdx<P: AsRef<Path>>(path: P) -> bool {
+        path.as_ref()
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("mdx"))
+    }
+
+    /// Check if a line starts an ESM block: `import` or `export` at the start of the line.
+    pub fn is_esm_start(line: &str) -> bool {
+        Self::ESM_KEYWORDS.iter().any(|keyword| {
+            line.strip_prefix(keyword)
+                .is_some_and(|rest| rest.starts_with([' ', '\t', '{', '*', '"', '\'']))
+        })
+    }
+
+    /// Check if a line starts a JSX block (`<Component`, `</Component>`, `<>`) or an expression
+    /// block (`{...}`).
+    fn is_jsx_start(line: &str) -> bool {
+        // Indented further than a block element, it's an indented code block.
+        let Some(line) = Taboc::strip_indent(line) else {
+            return false;
+        };
+
+        line.starts_with('{')
+            || line.strip_prefix('<').is_some_and(|tag| {
+                tag.starts_with(|c: char| c.is_alphabetic() || c == '/' || c == '>')
+            })
+    }
+
+    /// Check if a line is a part of an ESM, JSX or expression block. ESM blocks can't interrupt a
+    /// paragraph.
+    pub fn skip(&mut self, line: &str, in_paragraph: bool) -> bool {
+        if let Some(esm) = self.esm.as_mut() {
+            if line.trim().is_empty() && !esm.is_open() {
+                self.esm = None;
+            } else {
+                esm.feed(line);
+            }
+            return true;
+        }
+        if !in_paragraph && Self::is_esm_start(line) {
+            let mut esm = Lexer {
+                javascript: true,
+                ..Lexer::default()
+            };
+            esm.feed(line);
+            self.esm = Some(esm);
+            return true;
+        }
+
+        if !self.jsx.is_open() && !Self::is_jsx_start(line) {
+            return false;
+        }
+        self.jsx.feed(line);
+        true
+    }
+}
+
+/// A minimal JSX and JavaScript lexer. It only keeps track of whether the block is still open: an
+/// unclosed tag, an unclosed `{...}` expression or a string or comment in them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Lexer {
+    /// Plain JavaScript (ESM) instead of JSX.
+    javascript: bool,
+    /// The `{...}` nesting depth.
+    depth: usize,
+    in_tag: bool,
+    /// The quote of an open string (or template literal) in a tag or an expression.
+    quote: Option<char>,
+    /// In a `/* ... */` comment in an expression.
+    in_comment: bool,
+}
+
+impl Lexer {
+    fn is_open(&self) -> bool {
+        self.depth > 0 || self.in_tag || self.quote.is_some() || self.in_comment
+    }
+
+    /// Lex a line of the block.
+    fn feed(&mut self, line: &str) {
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if let Some(quote) = self.quote {
+                if c == '\\' {
+                    chars.next();
+                } else if c == quote {
+                    self.quote = None;
+                }
+                continue;
+            }
+            if self.in_comment {
+                if c == '*' && chars.next_if_eq(&'/').is_some() {
+                    self.in_comment = false;
+                }
+                continue;
+            }
+
+            // Strings are only strings in tags and expressions, the children of JSX elements are
+            // markdown text.
+            let in_javascript = self.javascript || self.depth > 0;
+            let in_code = in_javascript || self.in_tag;
+            match c {
+                '"' | '\'' if in_code => self.quote = Some(c),
+                '`' if in_javascript => self.quote = Some(c),
+                '/' if in_javascript && chars.next_if_eq(&'/').is_some() => break,
+                '/' if in_javascript && chars.next_if_eq(&'*').is_some() => self.in_comment = true,
+                '{' => self.depth += 1,
+                '}' => self.depth = self.depth.saturating_sub(1),
+                '<' if !in_javascript => self.in_tag = true,
+                '>' if !in_javascript => self.in_tag = false,
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MdxBlocks;
+
+    #[test]
+    fn esm_start() {
+        assert!(MdxBlocks::is_esm_start("import Tabs from '@theme/Tabs';"));
+        assert!(MdxBlocks::is_esm_start("export const meta = {"));
+        assert!(MdxBlocks::is_esm_start("import{a} from 'b'"));
+        assert!(!MdxBlocks::is_esm_start("important note"));
+        assert!(!MdxBlocks::is_esm_start(" import x from 'y'"));
+    }
+
+    #[test]
+    fn skip() {
+        let mut mdx = MdxBlocks::default();
+        let lines = [
+            ("export const meta = {", true),
+            ("# Not a heading", true),
+            ("", true),
+            ("  // Don't stop here.", true),
+            ("};", true),
+            ("", true),
+            ("<Note title=\"It's {1 + 1}\">", true),
+            ("# A heading in a component", false),
+            ("</Note>", true),
+            ("{/*", true),
+            ("# Commented out", true),
+            ("*/}", true),
+            ("<CodeBlock>{`", true),
+            ("# A shell comment", true),
+            ("`}</CodeBlock>", true),
+            ("# A heading", false),
+            ("\t<Note>", false),
+            ("   <Note>", true),
+            ("</Note>", true),
+        ];
+
+        for (line, skipped) in lines {
+            assert_eq!(mdx.skip(line, false), skipped, "{line}");
+        }
+    }
+}
@@ -20,6 +20,46 @@ pub mod args;
 /// The main logic in this applcation.
 pub mod toc;
 
+/// The heading model.
+pub mod heading;
+
+/// Heading text patterns used for selecting headings.
+pub mod pattern;
+
+/// YAML and TOML front matter detection.
+pub mod front_matter;
+
+/// Plain text rendering of the inline markdown in headings.
+pub mod inline;
+
+/// Line ending and byte order mark detection.
+pub mod text_format;
+
+/// Strict, lossy and legacy encoding decoding of documents.
+pub mod decoding;
+
+/// The backends finding the headings of a document.
+pub mod backend;
+
+/// Markdown flavors and their extension blocks.
+pub mod flavor;
+
+/// Skipping the ESM, JSX and expression blocks of MDX documents.
+pub mod mdx;
+
+/// Heading anchor (slug) strategies.
+pub mod slug;
+
+/// The list style of the table of contents.
+pub mod list_style;
+
+/// Hierarchical section numbering of the table of contents entries.
+pub mod numbering;
+
+/// Heading extraction with a spec-compliant CommonMark parser.
+#[cfg(feature = "commonmark")]
+pub mod commonmark;
+
 /// Utilities for checking if the changes from the args are commited or not.
 #[cfg(feature = "git")]
 pub mod git;
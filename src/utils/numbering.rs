@@ -0,0 +1,224 @@
+/*!
+Hierarchical section numbering of the table of contents entries (`1. Introduction`, `1.1 Scope`,
+`2.3.1 Wire format`).
+
+The numbers are computed from the heading tree, so skipped heading levels don't leave gaps. Each
+level of the numbering can have its own style and the headings above the starting level (like the
+title of the document) stay unnumbered:
+
+```
+use taboc::utils::numbering::{NumberStyle, Numbering};
+
+fn main() -> Result<(), anyhow::Error> {
+    let numbering = Numbering {
+        styles: vec![NumberStyle::Decimal, "alpha".parse()?, "roman".parse()?],
+        start_level: 2,
+    };
+    assert_eq!(numbering.format(&[2]), "2.");
+    assert_eq!(numbering.format(&[2, 3, 4]), "2.c.iv");
+
+    Ok(())
+}
+```
+*/
+
+use crate::{prelude::*, utils::heading::TocNode};
+use std::str::FromStr;
+
+/// How a level of the section numbers is written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NumberStyle {
+    /// `1`, `2`, `3`, ...
+    #[default]
+    Decimal,
+    /// `a`, `b`, ..., `z`, `aa`, `ab`, ...
+    LowerAlpha,
+    /// `A`, `B`, ..., `Z`, `AA`, `AB`, ...
+    UpperAlpha,
+    /// `i`, `ii`, `iii`, `iv`, ...
+    LowerRoman,
+    /// `I`, `II`, `III`, `IV`, ...
+    UpperRoman,
+}
+
+impl NumberStyle {
+    /// Roman numerals past it are written as decimal numbers.
+    const MAX_ROMAN: usize = 3999;
+    const ROMAN_NUMERALS: [(usize, &'static str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    fn alpha(mut n: usize) -> String {
+        let mut res = Vec::new();
+        while n > 0 {
+            n -= 1;
+            res.push(b'A' + (n % 26) as u8);
+            n /= 26;
+        }
+
+        res.iter().rev().map(|&b| char::from(b)).collect()
+    }
+
+    fn roman(mut n: usize) -> String {
+        if !(1..=Self::MAX_ROMAN).contains(&n) {
+            return n.to_string();
+        }
+
+        let mut res = String::new();
+        for (value, numeral) in Self::ROMAN_NUMERALS {
+            while n >= value {
+                res.push_str(numeral);
+                n -= value;
+            }
+        }
+
+        res
+    }
+
+    /// Write a 1-based number in this style.
+    pub fn format(self, n: usize) -> String {
+        match self {
+            Self::Decimal => n.to_string(),
+            Self::LowerAlpha => Self::alpha(n).to_ascii_lowercase(),
+            Self::UpperAlpha => Self::alpha(n),
+            Self::LowerRoman => Self::roman(n).to_ascii_lowercase(),
+            Self::UpperRoman => Self::roman(n),
+        }
+    }
+}
+
+impl FromStr for NumberStyle {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The single character forms are case-sensitive, like the `type` of an HTML `<ol>`.
+        match s {
+            "1" => return Ok(Self::Decimal),
+            "a" => return Ok(Self::LowerAlpha),
+            "A" => return Ok(Self::UpperAlpha),
+            "i" => return Ok(Self::LowerRoman),
+            "I" => return Ok(Self::UpperRoman),
+            _ => {}
+        }
+
+        match s.to_ascii_lowercase().as_str() {
+            "decimal" => Ok(Self::Decimal),
+            "alpha" | "lower-alpha" => Ok(Self::LowerAlpha),
+            "upper-alpha" => Ok(Self::UpperAlpha),
+            "roman" | "lower-roman" => Ok(Self::LowerRoman),
+            "upper-roman" => Ok(Self::UpperRoman),
+            _ => Err(anyhow!(
+                "Unknown number style: `{s}`. Expected `decimal`, `alpha`, `upper-alpha`, \
+                 `roman` or `upper-roman`."
+            )),
+        }
+    }
+}
+
+/// Hierarchical section numbering.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Numbering {
+    /// The style of each level of the numbers. The last one is used for the deeper levels too.
+    pub styles: Vec<NumberStyle>,
+    /// The heading level the numbering starts at. The headings above it aren't numbered and the
+    /// numbers of their subsections continue across them.
+    pub start_level: usize,
+}
+
+impl Default for Numbering {
+    fn default() -> Self {
+        Self {
+            styles: vec![NumberStyle::Decimal],
+            start_level: 1,
+        }
+    }
+}
+
+impl Numbering {
+    /// Write a section number (the 1-based position at each level). Top-level numbers end with a
+    /// `.` (`1.`), the nested ones don't (`1.2`).
+    pub fn format(&self, number: &[usize]) -> String {
+        let parts = number
+            .iter()
+            .enumerate()
+            .map(|(depth, &n)| {
+                let style = self.styles.get(depth).or(self.styles.last());
+                style.copied().unwrap_or_default().format(n)
+            })
+            .collect::<Vec<_>>();
+
+        match parts.as_slice() {
+            [part] => format!("{part}."),
+            _ => parts.join("."),
+        }
+    }
+
+    /// Prefix the text of the entries of the tree with their section numbers.
+    pub fn number(&self, nodes: &mut [TocNode]) {
+        self.number_nodes(nodes, &[], &mut 0);
+    }
+
+    fn number_nodes(&self, nodes: &mut [TocNode], parent: &[usize], counter: &mut usize) {
+        for node in nodes {
+            if node.heading.level < self.start_level {
+                self.number_nodes(&mut node.children, parent, counter);
+                continue;
+            }
+
+            *counter += 1;
+            let number = [parent, &[*counter]].concat();
+            node.heading.text = format!("{} {}", self.format(&number), node.heading.text);
+            self.number_nodes(&mut node.children, &number, &mut 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NumberStyle, Numbering};
+
+    #[test]
+    fn number_style() {
+        assert_eq!(NumberStyle::LowerAlpha.format(1), "a");
+        assert_eq!(NumberStyle::UpperAlpha.format(26), "Z");
+        assert_eq!(NumberStyle::LowerAlpha.format(27), "aa");
+        assert_eq!(NumberStyle::LowerAlpha.format(703), "aaa");
+        assert_eq!(NumberStyle::UpperRoman.format(1994), "MCMXCIV");
+        assert_eq!(NumberStyle::LowerRoman.format(4), "iv");
+        assert_eq!(NumberStyle::LowerRoman.format(4000), "4000");
+        assert_eq!(
+            "I".parse::<NumberStyle>().ok(),
+            Some(NumberStyle::UpperRoman)
+        );
+        assert_eq!(
+            "i".parse::<NumberStyle>().ok(),
+            Some(NumberStyle::LowerRoman)
+        );
+        assert!("greek".parse::<NumberStyle>().is_err());
+    }
+
+    #[test]
+    fn format() {
+        let numbering = Numbering::default();
+        assert_eq!(numbering.format(&[1]), "1.");
+        assert_eq!(numbering.format(&[2, 3, 1]), "2.3.1");
+
+        let numbering = Numbering {
+            styles: vec![NumberStyle::UpperRoman, NumberStyle::LowerAlpha],
+            start_level: 1,
+        };
+        assert_eq!(numbering.format(&[4, 2, 3]), "IV.b.c");
+    }
+}
@@ -0,0 +1,65 @@
+/*!
+Heading text patterns for the include and exclude selection rules.
+
+```
+use taboc::utils::pattern::Pattern;
+
+fn main() -> Result<(), anyhow::Error> {
+    let glob: Pattern = "licen[sc]e".parse()?;
+    assert!(glob.is_match("License"));
+
+    let regex: Pattern = "regex:^Contribut(e|ing)$".parse()?;
+    assert!(regex.is_match("Contributing"));
+    assert!(!regex.is_match("contributing"));
+
+    Ok(())
+}
+```
+*/
+
+use crate::prelude::*;
+use globset::{GlobBuilder, GlobMatcher};
+use regex::Regex;
+use std::str::FromStr;
+
+/// A pattern which is matched against the plain text of a heading.
+///
+/// Patterns prefixed with `regex:` are regular expressions which can match anywhere in the text.
+/// Everything else is a case-insensitive glob which has to match the whole text. Use the `glob:`
+/// prefix for globs which start with `regex:`.
+#[derive(Clone, Debug)]
+pub enum Pattern {
+    Glob(GlobMatcher),
+    Regex(Regex),
+}
+
+impl Pattern {
+    const GLOB_PREFIX: &'static str = "glob:";
+    const REGEX_PREFIX: &'static str = "regex:";
+
+    /// Check if the heading text matches the pattern.
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            Self::Glob(glob) => glob.is_match(text),
+            Self::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(regex) = s.strip_prefix(Self::REGEX_PREFIX) {
+            return Ok(Self::Regex(Regex::new(regex)?));
+        }
+
+        let glob = s.strip_prefix(Self::GLOB_PREFIX).unwrap_or(s);
+        Ok(Self::Glob(
+            GlobBuilder::new(glob)
+                .case_insensitive(true)
+                .build()?
+                .compile_matcher(),
+        ))
+    }
+}
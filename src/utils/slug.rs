@@ -0,0 +1,734 @@
+/*!
+Slug strategies. Every platform renders the heading anchors a bit differently, so the slugs have to
+match the one the document is read on.
+
+There are presets for the code hosting platforms ([`GitHub`], [`GitLab`]), the documentation
+generators ([`Rustdoc`], [`MdBook`], [`Pandoc`] and [`PythonMarkdown`], used by MkDocs) and the
+static site generators ([`Hugo`], [`Jekyll`], [`Kramdown`], [`Docusaurus`] and [`Zola`]). Any of
+them can make ASCII-only slugs with [`Transliterated`].
+
+Custom slug strategies can be used by implementing the [`Slugger`] trait:
+
+```
+use taboc::{prelude::*, utils::slug::Slugger};
+
+/// Wiki style slugs: `Hello World` -> `Hello_World`.
+struct Wiki;
+
+impl Slugger for Wiki {
+    fn slug(&self, text: &str) -> String {
+        text.replace(' ', "_")
+    }
+
+    fn disambiguate(&self, slug: &str, count: usize) -> String {
+        format!("{slug}_{}", count + 1)
+    }
+}
+
+fn main() -> Result<(), Error> {
+    let taboc = Taboc::new(std::fs::File::open("mock_data/README.md")?, 6).with_slugger(Wiki);
+    assert!(taboc.parse()?.contains("(#Heading_Two_Number_2)"));
+
+    Ok(())
+}
+```
+*/
+
+use crate::prelude::*;
+use regex::Regex;
+use std::{str::FromStr, sync::LazyLock};
+use unicode_normalization::UnicodeNormalization;
+
+/// Compile a constant pattern.
+#[allow(clippy::expect_used, reason = "The patterns are constants.")]
+fn pattern(pattern: &str) -> Regex {
+    Regex::new(pattern).expect("Valid slug pattern.")
+}
+
+/// Anything that isn't a word character (Ruby's `\p{Word}`), a hyphen or a space. GitHub and
+/// GitLab remove all of it.
+static WORD_PUNCTUATION: LazyLock<Regex> =
+    LazyLock::new(|| pattern(r"[^\p{L}\p{M}\p{Nd}\p{Pc}\- ]"));
+
+/// Makes the anchors of the headings. It's `Send` and `Sync`, so [`Taboc`] is too.
+pub trait Slugger: Send + Sync {
+    /// Make the slug of a heading from its plain text.
+    fn slug(&self, text: &str) -> String;
+
+    /// Make a repeated slug unique. The `count` is how many times the slug was repeated before
+    /// (starting from 1). Returns `{slug}-{count}` by default.
+    ///
+    /// NOTE: The result is checked for collisions with the other slugs of the document too (unless
+    /// the slugger doesn't [check them](Slugger::checks_collisions)), the `count` keeps increasing
+    /// until it's unique.
+    fn disambiguate(&self, slug: &str, count: usize) -> String {
+        format!("{slug}-{count}")
+    }
+
+    /// Whether a disambiguated slug is checked for collisions with the other slugs of the
+    /// document. Only the repeats of the same slug are counted otherwise, so `Intro`, `Intro` and
+    /// `Intro 1` are all `intro-1` after the first one. It is by default.
+    fn checks_collisions(&self) -> bool {
+        true
+    }
+
+    /// Whether the alt text of the images in a heading is a part of its slug. It is by default.
+    fn includes_image_alt(&self) -> bool {
+        true
+    }
+
+    /// Whether the slug is made from the markdown source of the heading (without its explicit id)
+    /// instead of its plain text. It isn't by default.
+    fn slugs_raw_text(&self) -> bool {
+        false
+    }
+
+    /// Ids which are already taken on the rendered page, so the headings slugged to them are
+    /// disambiguated even when they're the first ones. None by default.
+    fn reserved(&self) -> &[&str] {
+        &[]
+    }
+}
+
+/// GitHub style slugs. The text is NFC normalized and lowercased, everything but letters, marks,
+/// decimal digits, connector punctuation (`_`), `-` and spaces is removed (emoji and Unicode
+/// punctuation included) and spaces turn into `-`. Images don't have any text in the rendered
+/// heading, so their alt text isn't a part of the slug.
+///
+/// Refer to the `TableOfContentsFilter` of `html-pipeline` and to
+/// [`github-slugger`](https://github.com/Flet/github-slugger).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GitHub;
+
+impl GitHub {
+    /// Variation selectors only pick how the emoji before them is drawn (`❤️`), they're removed
+    /// along with it even though they're marks.
+    const VARIATION_SELECTORS: std::ops::RangeInclusive<char> = '\u{fe00}'..='\u{fe0f}';
+}
+
+impl Slugger for GitHub {
+    fn slug(&self, text: &str) -> String {
+        let lowercase = text
+            .nfc()
+            .filter(|c| !Self::VARIATION_SELECTORS.contains(c))
+            .collect::<String>()
+            .to_lowercase();
+        WORD_PUNCTUATION
+            .replace_all(&lowercase, "")
+            .replace(' ', "-")
+    }
+
+    fn includes_image_alt(&self) -> bool {
+        false
+    }
+
+    fn checks_collisions(&self) -> bool {
+        false
+    }
+}
+
+/// GitLab style slugs. Punctuation is removed (except for `-` and `_`), repeated hyphens are
+/// collapsed and numeric slugs get an `anchor-` prefix.
+///
+/// Refer to `Gitlab::Utils::Markdown#string_to_anchor`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GitLab;
+
+impl Slugger for GitLab {
+    fn slug(&self, text: &str) -> String {
+        let lowercase = text.trim().to_lowercase();
+        let hyphenated = WORD_PUNCTUATION
+            .replace_all(&lowercase, "")
+            .replace(' ', "-");
+
+        let mut res = String::with_capacity(hyphenated.len());
+        for c in hyphenated.chars() {
+            if !(c == '-' && res.ends_with('-')) {
+                res.push(c);
+            }
+        }
+
+        if !res.is_empty() && res.chars().all(|c| c.is_ascii_digit()) {
+            res.insert_str(0, "anchor-");
+        }
+
+        res
+    }
+
+    fn includes_image_alt(&self) -> bool {
+        false
+    }
+}
+
+/// rustdoc style slugs (`#![doc = include_str!("../README.md")]`, docs.rs). Alphanumeric
+/// characters, `-` and `_` are kept (only the ASCII ones are lowercased) and ASCII whitespace turns
+/// into `-`.
+///
+/// Refer to `librustdoc::html::markdown::IdMap`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rustdoc;
+
+impl Rustdoc {
+    /// The ids of the rustdoc page itself (the JavaScript, the page template and the item
+    /// sections).
+    ///
+    /// NOTE: This is `DEFAULT_ID_MAP` of `librustdoc::html::markdown` as of rustdoc 1.95, except
+    /// for `themeStyle`, which lowercase slugs can't collide with.
+    const RESERVED: &'static [&'static str] = &[
+        "help",
+        "settings",
+        "not-displayed",
+        "alternative-display",
+        "search",
+        "crate-search",
+        "crate-search-div",
+        "settings-menu",
+        "help-button",
+        "sidebar-button",
+        "main-content",
+        "toggle-all-docs",
+        "all-types",
+        "default-settings",
+        "sidebar-vars",
+        "copy-path",
+        "rustdoc-toc",
+        "rustdoc-modnav",
+        "fields",
+        "variants",
+        "implementors-list",
+        "synthetic-implementors-list",
+        "foreign-impls",
+        "implementations",
+        "trait-implementations",
+        "synthetic-implementations",
+        "blanket-implementations",
+        "required-associated-types",
+        "provided-associated-types",
+        "provided-associated-consts",
+        "required-associated-consts",
+        "required-methods",
+        "provided-methods",
+        "dyn-compatibility",
+        "implementors",
+        "synthetic-implementors",
+        "implementations-list",
+        "trait-implementations-list",
+        "synthetic-implementations-list",
+        "blanket-implementations-list",
+        "deref-methods",
+        "layout",
+        "aliased-type",
+    ];
+}
+
+impl Slugger for Rustdoc {
+    fn slug(&self, text: &str) -> String {
+        text.chars()
+            .filter_map(|c| {
+                if c.is_alphanumeric() || c == '-' || c == '_' {
+                    Some(c.to_ascii_lowercase())
+                } else if c.is_ascii_whitespace() {
+                    Some('-')
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn reserved(&self) -> &[&str] {
+        Self::RESERVED
+    }
+}
+
+/// mdBook style slugs. Like [`Rustdoc`], except that any whitespace turns into `-` and there are
+/// no reserved ids.
+///
+/// Refer to `mdbook::utils::normalize_id`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MdBook;
+
+impl Slugger for MdBook {
+    fn slug(&self, text: &str) -> String {
+        text.chars()
+            .filter_map(|c| {
+                if c.is_alphanumeric() || c == '-' || c == '_' {
+                    Some(c.to_ascii_lowercase())
+                } else if c.is_whitespace() {
+                    Some('-')
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Pandoc style slugs (the `auto_identifiers` extension). Alphanumeric characters, `_`, `-` and
+/// `.` are kept, the words are joined with `-` and everything up to the first letter is removed.
+/// Headings without any letters get the `section` id.
+///
+/// Refer to `Text.Pandoc.Shared.inlineListToIdentifier`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pandoc;
+
+impl Slugger for Pandoc {
+    fn slug(&self, text: &str) -> String {
+        let text = text
+            .to_lowercase()
+            .chars()
+            .filter(|&c| c.is_alphanumeric() || c.is_whitespace() || "_-.".contains(c))
+            .collect::<String>();
+        let slug = text.split_whitespace().collect::<Vec<_>>().join("-");
+
+        match slug.trim_start_matches(|c: char| !c.is_alphabetic()) {
+            "" => "section".to_owned(),
+            slug => slug.to_owned(),
+        }
+    }
+}
+
+/// python-markdown style slugs (the `toc` extension, used by MkDocs). The text is transliterated
+/// to ASCII by dropping the accents, punctuation is removed and the runs of whitespace and `-` turn
+/// into a single `-`. Repeated slugs get a `_1`, `_2`, ... suffix, or their `_N` suffix is
+/// incremented, and headings without any ASCII text get the `_1`, `_2`, ... ids.
+///
+/// Refer to `markdown.extensions.toc.slugify` and `markdown.extensions.toc.unique`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PythonMarkdown;
+
+impl Slugger for PythonMarkdown {
+    fn slug(&self, text: &str) -> String {
+        let ascii = text
+            .nfkd()
+            .filter(|&c| c.is_ascii_alphanumeric() || c.is_ascii_whitespace() || "_-".contains(c))
+            .collect::<String>()
+            .to_ascii_lowercase();
+
+        let mut res = String::with_capacity(ascii.len());
+        for c in ascii.trim().chars() {
+            if c != '-' && !c.is_ascii_whitespace() {
+                res.push(c);
+            } else if !res.ends_with('-') {
+                res.push('-');
+            }
+        }
+
+        // `unique` doesn't allow empty ids.
+        if res.is_empty() {
+            return self.disambiguate(&res, 1);
+        }
+
+        res
+    }
+
+    fn disambiguate(&self, slug: &str, count: usize) -> String {
+        let suffix = slug
+            .rsplit_once('_')
+            .filter(|(_, n)| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|(base, n)| Some((base, n.parse::<usize>().ok()?)));
+
+        match suffix {
+            Some((base, n)) => format!("{base}_{}", n + count),
+            None => format!("{slug}_{count}"),
+        }
+    }
+}
+
+/// Hugo style slugs (goldmark with the default `github` `autoHeadingIDType`). Letters, digits and
+/// `_` are kept, spaces and `-` turn into `-` and everything else is removed. Headings without
+/// any of them get the `heading` id.
+///
+/// Refer to `hugo/markup/goldmark/autoid.go`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Hugo;
+
+impl Slugger for Hugo {
+    fn slug(&self, text: &str) -> String {
+        /// Go's `unicode.IsLetter` and `unicode.IsDigit`, along with `_`, `-` and spaces.
+        static PUNCTUATION: LazyLock<Regex> = LazyLock::new(|| pattern(r"[^\p{L}\p{Nd}_\- ]"));
+
+        let slug = PUNCTUATION
+            .replace_all(&text.to_lowercase(), "")
+            .replace(' ', "-");
+
+        match slug.as_str() {
+            "" => "heading".to_owned(),
+            _ => slug,
+        }
+    }
+}
+
+/// Jekyll style slugs (kramdown with its `GFM` input, the Jekyll and GitHub Pages default). Like
+/// [`GitHub`], except that the text isn't normalized, tabs turn into `-` too and the slug is made
+/// from the markdown source of the heading (`[Docs](https://docs.rs)` -> `docshttpsdocsrs`).
+///
+/// Refer to `Kramdown::Parser::GFM#generate_gfm_header_id`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Jekyll;
+
+impl Slugger for Jekyll {
+    fn slug(&self, text: &str) -> String {
+        /// Anything that isn't a word character, a hyphen, a space or a tab.
+        static PUNCTUATION: LazyLock<Regex> =
+            LazyLock::new(|| pattern(r"[^\p{L}\p{M}\p{Nd}\p{Pc}\- \t]"));
+
+        PUNCTUATION
+            .replace_all(&text.to_lowercase(), "")
+            .replace([' ', '\t'], "-")
+    }
+
+    fn slugs_raw_text(&self) -> bool {
+        true
+    }
+}
+
+/// kramdown style slugs (its own `kramdown` input with `auto_ids`). Only ASCII letters, digits,
+/// spaces and `-` of the markdown source of the heading are kept, everything up to the first
+/// ASCII letter is removed and spaces turn into `-`. Headings without any letters get the
+/// `section` id.
+///
+/// Refer to `Kramdown::Converter::Base#basic_generate_id`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Kramdown;
+
+impl Slugger for Kramdown {
+    fn slug(&self, text: &str) -> String {
+        let slug = text
+            .trim_start_matches(|c: char| !c.is_ascii_alphabetic())
+            .chars()
+            .filter_map(|c| match c {
+                ' ' => Some('-'),
+                '-' => Some(c),
+                c if c.is_ascii_alphanumeric() => Some(c.to_ascii_lowercase()),
+                _ => None,
+            })
+            .collect::<String>();
+
+        match slug.as_str() {
+            "" => "section".to_owned(),
+            _ => slug,
+        }
+    }
+
+    fn slugs_raw_text(&self) -> bool {
+        true
+    }
+}
+
+/// Docusaurus style slugs ([`github-slugger`](https://github.com/Flet/github-slugger)). Like
+/// [`GitHub`], except that the text isn't normalized, all the numbers are kept (`½`, `Ⅻ`, not only
+/// the decimal digits) and the alt text of the images is a part of it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Docusaurus;
+
+impl Slugger for Docusaurus {
+    fn slug(&self, text: &str) -> String {
+        /// Anything that isn't a letter, a mark, a number, connector punctuation, a hyphen or a
+        /// space.
+        static PUNCTUATION: LazyLock<Regex> =
+            LazyLock::new(|| pattern(r"[^\p{L}\p{M}\p{N}\p{Pc}\- ]"));
+
+        PUNCTUATION
+            .replace_all(&text.to_lowercase(), "")
+            .replace(' ', "-")
+    }
+}
+
+/// Zola style slugs (the default `slugify.anchors = "on"`). The text is transliterated to ASCII
+/// and the runs of anything but ASCII letters and digits turn into a single `-`.
+///
+/// Refer to the [`slug`](https://docs.rs/slug) crate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Zola;
+
+impl Slugger for Zola {
+    fn slug(&self, text: &str) -> String {
+        ::slug::slugify(text)
+    }
+}
+
+/// Transliterates the heading text to ASCII before making its slug with another slugger
+/// (`Größe` -> `grosse`, `Привет` -> `privet`, `日本語` -> `ri-ben-yu`), for the platforms which
+/// don't support non-ASCII anchors.
+///
+/// Refer to the [`deunicode`](https://docs.rs/deunicode) crate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Transliterated<S>(pub S);
+
+impl<S: Slugger> Slugger for Transliterated<S> {
+    fn slug(&self, text: &str) -> String {
+        self.0.slug(&deunicode::deunicode(text))
+    }
+
+    fn disambiguate(&self, slug: &str, count: usize) -> String {
+        self.0.disambiguate(slug, count)
+    }
+
+    fn includes_image_alt(&self) -> bool {
+        self.0.includes_image_alt()
+    }
+
+    fn checks_collisions(&self) -> bool {
+        self.0.checks_collisions()
+    }
+
+    fn slugs_raw_text(&self) -> bool {
+        self.0.slugs_raw_text()
+    }
+
+    fn reserved(&self) -> &[&str] {
+        self.0.reserved()
+    }
+}
+
+/// The built-in slug strategies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SlugStyle {
+    #[default]
+    GitHub,
+    GitLab,
+    Rustdoc,
+    MdBook,
+    Pandoc,
+    PythonMarkdown,
+    Hugo,
+    Jekyll,
+    Kramdown,
+    Docusaurus,
+    Zola,
+}
+
+impl SlugStyle {
+    fn slugger(self) -> &'static dyn Slugger {
+        match self {
+            Self::GitHub => &GitHub,
+            Self::GitLab => &GitLab,
+            Self::Rustdoc => &Rustdoc,
+            Self::MdBook => &MdBook,
+            Self::Pandoc => &Pandoc,
+            Self::PythonMarkdown => &PythonMarkdown,
+            Self::Hugo => &Hugo,
+            Self::Jekyll => &Jekyll,
+            Self::Kramdown => &Kramdown,
+            Self::Docusaurus => &Docusaurus,
+            Self::Zola => &Zola,
+        }
+    }
+}
+
+impl Slugger for SlugStyle {
+    fn slug(&self, text: &str) -> String {
+        self.slugger().slug(text)
+    }
+
+    fn disambiguate(&self, slug: &str, count: usize) -> String {
+        self.slugger().disambiguate(slug, count)
+    }
+
+    fn includes_image_alt(&self) -> bool {
+        self.slugger().includes_image_alt()
+    }
+
+    fn checks_collisions(&self) -> bool {
+        self.slugger().checks_collisions()
+    }
+
+    fn slugs_raw_text(&self) -> bool {
+        self.slugger().slugs_raw_text()
+    }
+
+    fn reserved(&self) -> &[&str] {
+        self.slugger().reserved()
+    }
+}
+
+impl FromStr for SlugStyle {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "github" => Ok(Self::GitHub),
+            "gitlab" => Ok(Self::GitLab),
+            "rustdoc" => Ok(Self::Rustdoc),
+            "mdbook" => Ok(Self::MdBook),
+            "pandoc" => Ok(Self::Pandoc),
+            "python-markdown" | "mkdocs" => Ok(Self::PythonMarkdown),
+            "hugo" => Ok(Self::Hugo),
+            "jekyll" => Ok(Self::Jekyll),
+            "kramdown" => Ok(Self::Kramdown),
+            "docusaurus" => Ok(Self::Docusaurus),
+            "zola" => Ok(Self::Zola),
+            _ => Err(anyhow!(
+                "Unknown slug style: `{s}`. Expected `github`, `gitlab`, `rustdoc`, `mdbook`, \
+                 `pandoc`, `python-markdown`, `hugo`, `jekyll`, `kramdown`, `docusaurus` or \
+                 `zola`."
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Docusaurus, GitHub, GitLab, Hugo, Jekyll, Kramdown, MdBook, Pandoc, PythonMarkdown,
+        Rustdoc, Slugger, Transliterated, Zola,
+    };
+
+    #[test]
+    fn github() {
+        let slug = |text| GitHub.slug(text);
+
+        assert_eq!(slug("🚀 Launch"), "-launch");
+        assert_eq!(slug("Cafe\u{301}"), "caf\u{e9}");
+        assert_eq!(slug("（全角）括弧"), "全角括弧");
+        assert!(!GitHub.includes_image_alt());
+    }
+
+    #[test]
+    fn gitlab() {
+        let slug = |text| GitLab.slug(text);
+
+        assert_eq!(slug("This - is a test"), "this-is-a-test");
+        assert_eq!(slug("  Hello, World!  "), "hello-world");
+        assert_eq!(slug("snake_case & more"), "snake_case-more");
+        assert_eq!(slug("Über Straße"), "über-straße");
+        assert_eq!(slug("日本語 タイトル"), "日本語-タイトル");
+        assert_eq!(slug("1.0"), "anchor-10");
+        assert_eq!(slug("v1.0"), "v10");
+        assert_eq!(slug("Emoji 🎉 party"), "emoji-party");
+        assert_eq!(GitLab.disambiguate("intro", 2), "intro-2");
+    }
+
+    #[test]
+    fn rustdoc() {
+        let slug = |text| Rustdoc.slug(text);
+
+        assert_eq!(slug("What's new in v2.0?"), "whats-new-in-v20");
+        assert_eq!(slug("Install -- the easy way"), "install----the-easy-way");
+        assert_eq!(slug("C++ & Rust"), "c--rust");
+        assert_eq!(slug("Über Straße"), "Über-straße");
+        assert_eq!(slug("Non\u{a0}breaking"), "nonbreaking");
+        assert_eq!(slug("Emoji 🎉 party"), "emoji--party");
+        assert!(Rustdoc.reserved().contains(&slug("Help button").as_str()));
+        assert!(Rustdoc
+            .reserved()
+            .contains(&slug("Implementors list").as_str()));
+    }
+
+    #[test]
+    fn mdbook() {
+        let slug = |text| MdBook.slug(text);
+
+        assert_eq!(slug("What's new in v2.0?"), "whats-new-in-v20");
+        assert_eq!(slug("C++ & Rust"), "c--rust");
+        assert_eq!(slug("Über Straße"), "Über-straße");
+        assert_eq!(slug("Non\u{a0}breaking"), "non-breaking");
+        assert!(MdBook.reserved().is_empty());
+    }
+
+    #[test]
+    fn pandoc() {
+        let slug = |text| Pandoc.slug(text);
+
+        assert_eq!(slug("What's new in v2.0?"), "whats-new-in-v2.0");
+        assert_eq!(slug("Install -- the easy way"), "install----the-easy-way");
+        assert_eq!(slug("  C++   &  Rust "), "c-rust");
+        assert_eq!(slug("3. Usage"), "usage");
+        assert_eq!(slug("Über Straße"), "über-straße");
+        assert_eq!(slug("2024"), "section");
+        assert_eq!(slug("?!"), "section");
+    }
+
+    #[test]
+    fn python_markdown() {
+        let slug = |text| PythonMarkdown.slug(text);
+
+        assert_eq!(slug("What's new in v2.0?"), "whats-new-in-v20");
+        assert_eq!(slug("Install -- the easy way"), "install-the-easy-way");
+        assert_eq!(slug("C++ & Rust"), "c-rust");
+        assert_eq!(slug("Über Straße"), "uber-strae");
+        assert_eq!(slug("žlutý kůň"), "zluty-kun");
+        assert_eq!(slug("日本語"), "_1");
+        assert_eq!(PythonMarkdown.disambiguate("intro", 2), "intro_2");
+        assert_eq!(PythonMarkdown.disambiguate("intro_1", 1), "intro_2");
+        assert_eq!(PythonMarkdown.disambiguate("_1", 2), "_3");
+        assert_eq!(PythonMarkdown.disambiguate("v_1a", 1), "v_1a_1");
+    }
+
+    #[test]
+    fn hugo() {
+        let slug = |text| Hugo.slug(text);
+
+        assert_eq!(slug("What's new in v2.0?"), "whats-new-in-v20");
+        assert_eq!(slug("Install -- the easy way"), "install----the-easy-way");
+        assert_eq!(slug("snake_case names"), "snake_case-names");
+        assert_eq!(slug("3. Usage"), "3-usage");
+        assert_eq!(slug("Über Straße"), "über-straße");
+        assert_eq!(slug("Ⅻ ½ 🎉"), "--");
+        assert_eq!(slug("?!"), "heading");
+    }
+
+    #[test]
+    fn jekyll() {
+        let slug = |text| Jekyll.slug(text);
+
+        assert_eq!(slug("What's new in v2.0?"), "whats-new-in-v20");
+        assert_eq!(slug("C++ & Rust"), "c--rust");
+        assert_eq!(slug("snake_case\tnames"), "snake_case-names");
+        assert_eq!(slug("[Docs](https://docs.rs)"), "docshttpsdocsrs");
+        assert_eq!(slug("Über Straße"), "über-straße");
+        assert_eq!(slug("Party 🎉 time"), "party--time");
+    }
+
+    #[test]
+    fn kramdown() {
+        let slug = |text| Kramdown.slug(text);
+
+        assert_eq!(slug("What's new in v2.0?"), "whats-new-in-v20");
+        assert_eq!(slug("Install -- the easy way"), "install----the-easy-way");
+        assert_eq!(slug("snake_case names"), "snakecase-names");
+        assert_eq!(slug("3. Usage"), "usage");
+        assert_eq!(slug("Über Straße"), "ber-strae");
+        assert_eq!(slug("2024"), "section");
+    }
+
+    #[test]
+    fn docusaurus() {
+        let slug = |text| Docusaurus.slug(text);
+
+        assert_eq!(slug("What's new in v2.0?"), "whats-new-in-v20");
+        assert_eq!(slug("C++ & Rust"), "c--rust");
+        assert_eq!(slug("snake_case names"), "snake_case-names");
+        assert_eq!(slug("Über Straße"), "über-straße");
+        assert_eq!(slug("Party 🎉 time"), "party--time");
+        assert_eq!(slug("Ⅻ ½ 🎉"), "ⅻ-½-");
+    }
+
+    #[test]
+    fn zola() {
+        let slug = |text| Zola.slug(text);
+
+        assert_eq!(slug("What's new in v2.0?"), "what-s-new-in-v2-0");
+        assert_eq!(slug("Install -- the easy way"), "install-the-easy-way");
+        assert_eq!(slug("snake_case names"), "snake-case-names");
+        assert_eq!(slug("3. Usage"), "3-usage");
+        assert_eq!(slug("Über Straße"), "uber-strasse");
+        assert_eq!(slug("日本語"), "ri-ben-yu");
+    }
+
+    #[test]
+    fn transliterated() {
+        let slug = |text| Transliterated(GitHub).slug(text);
+
+        assert_eq!(slug("Größe"), "grosse");
+        assert_eq!(slug("Привет"), "privet");
+        assert_eq!(slug("日本語"), "ri-ben-yu");
+        assert_eq!(slug("Café au lait"), "cafe-au-lait");
+        assert_eq!(
+            Transliterated(Pandoc).slug("Übersicht 2.0"),
+            "ubersicht-2.0"
+        );
+        assert_eq!(Transliterated(PythonMarkdown).disambiguate("a", 1), "a_1");
+    }
+}
@@ -0,0 +1,118 @@
+/*!
+Line ending and byte order mark detection.
+
+Markdown documents written on Windows usually end their lines with `\r\n` and some editors start
+them with a UTF-8 byte order mark. Neither of them is a part of the document text, so they're
+stripped before parsing and the table of contents is written back with the document's own line
+endings.
+*/
+
+use crate::prelude::*;
+use std::{borrow::Cow, io::BufRead};
+
+/// The UTF-8 encoded byte order mark.
+pub const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// The line ending style of a document.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+
+    /// Convert the `\n` line endings of the `text` to this line ending.
+    pub fn convert(self, text: &str) -> Cow<'_, str> {
+        match self {
+            Self::Lf => Cow::Borrowed(text),
+            Self::CrLf => Cow::Owned(text.replace('\n', self.as_str())),
+        }
+    }
+
+    /// Strip the line ending of a line read up to (and including) its `\n`.
+    pub fn strip(line: &[u8]) -> &[u8] {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        line.strip_suffix(b"\r").unwrap_or(line)
+    }
+}
+
+/// The line ending style and the byte order mark of a document.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextFormat {
+    /// Whether the document starts with a byte order mark.
+    pub bom: bool,
+    /// The line ending of the first line. Documents with mixed line endings are written with the
+    /// one they start with.
+    pub line_ending: LineEnding,
+}
+
+impl TextFormat {
+    /// Detect the text format from the first line of a document.
+    pub fn detect<R: BufRead>(mut reader: R) -> Result<Self, Error> {
+        let mut line_buf = Vec::new();
+        reader.read_until(b'\n', &mut line_buf)?;
+
+        Ok(Self {
+            bom: line_buf.starts_with(BOM),
+            line_ending: if line_buf.ends_with(b"\r\n") {
+                LineEnding::CrLf
+            } else {
+                LineEnding::Lf
+            },
+        })
+    }
+
+    /// The length of the byte order mark in bytes, if there's one.
+    pub fn bom_len(self) -> u64 {
+        if self.bom {
+            BOM.len() as u64
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineEnding, TextFormat};
+
+    #[test]
+    fn detect() -> Result<(), anyhow::Error> {
+        assert_eq!(
+            TextFormat::detect("# Title\n\n## Heading\r\n".as_bytes())?,
+            TextFormat {
+                bom: false,
+                line_ending: LineEnding::Lf
+            }
+        );
+        assert_eq!(
+            TextFormat::detect("\u{feff}# Title\r\n\n".as_bytes())?,
+            TextFormat {
+                bom: true,
+                line_ending: LineEnding::CrLf
+            }
+        );
+        assert_eq!(TextFormat::detect("".as_bytes())?, TextFormat::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn convert() {
+        assert_eq!(LineEnding::Lf.convert("a\n\nb"), "a\n\nb");
+        assert_eq!(LineEnding::CrLf.convert("a\n\nb"), "a\r\n\r\nb");
+        assert_eq!(LineEnding::strip(b"a\r\n"), b"a");
+        assert_eq!(LineEnding::strip(b"a\n"), b"a");
+        assert_eq!(LineEnding::strip(b"a"), b"a");
+    }
+}
@@ -1,18 +1,337 @@
 #[cfg(feature = "memmap2")]
 use memmap2::MmapMut;
 #[cfg(feature = "memmap2")]
-use std::cmp::min;
+use std::cmp::max;
 
-use crate::prelude::*;
+use crate::{
+    prelude::*,
+    utils::{
+        backend::Backend,
+        decoding::Decoding,
+        flavor::{Extension, Extensions, Flavor},
+        front_matter::FrontMatter,
+        heading::{Heading, TocNode},
+        inline,
+        list_style::ListStyle,
+        mdx::MdxBlocks,
+        numbering::Numbering,
+        pattern::Pattern,
+        slug::{SlugStyle, Slugger},
+        text_format::{LineEnding, TextFormat, BOM},
+    },
+};
 use std::{
-    cell::Cell,
-    fs::OpenOptions,
-    io::{BufRead, BufReader, Seek, Write},
-    path::Path,
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Seek, SeekFrom},
+    ops::Range,
+    path::{Path, PathBuf},
 };
 
 #[cfg(not(feature = "memmap2"))]
-use std::io::Read;
+use std::io::{Read, Write};
+
+#[cfg(feature = "commonmark")]
+use crate::utils::commonmark::{self, Document};
+
+/// An open fenced code block. A fence can only be closed by the same character, repeated at least
+/// as many times as in the opening fence.
+///
+/// Refer to the [CommonMark spec](https://spec.commonmark.org/0.31.2/#fenced-code-blocks).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Fence {
+    marker: char,
+    len: usize,
+}
+
+impl Fence {
+    const MARKERS: [char; 2] = ['`', '~'];
+    const MIN_LEN: usize = 3;
+
+    /// Get the marker character and its repetition count at the start of a line, allowing up to
+    /// three spaces of indentation.
+    fn marker_run(line: &str) -> Option<(char, usize, &str)> {
+        let line = Taboc::strip_indent(line)?;
+        let marker = line.chars().next().filter(|c| Self::MARKERS.contains(c))?;
+        let len = line.chars().take_while(|c| *c == marker).count();
+
+        (len >= Self::MIN_LEN).then(|| (marker, len, &line[len..]))
+    }
+
+    /// Try to open a fenced code block. The info string of a backtick fence can't contain
+    /// backticks (otherwise it'd be an inline code span).
+    fn open(line: &str) -> Option<Self> {
+        let (marker, len, info) = Self::marker_run(line)?;
+        if marker == '`' && info.contains('`') {
+            return None;
+        }
+
+        Some(Self { marker, len })
+    }
+
+    /// Check if a line closes this fence. Closing fences can't have an info string.
+    fn is_closed_by(&self, line: &str) -> bool {
+        Self::marker_run(line).is_some_and(|(marker, len, rest)| {
+            marker == self.marker && len >= self.len && rest.trim().is_empty()
+        })
+    }
+}
+
+/// An open HTML block. Its contents are raw HTML so they can't contain markdown headings.
+///
+/// Refer to the [CommonMark spec](https://spec.commonmark.org/0.31.2/#html-blocks).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HtmlBlock {
+    /// Comments, `<pre>`, `<script>`, processing instructions, declarations and `CDATA` end at the
+    /// first line containing the end marker.
+    Until(&'static str),
+    /// Block-level tags (like `<details>` or `<div>`) and other complete tags end at a blank line.
+    UntilBlankLine,
+}
+
+impl HtmlBlock {
+    /// Tags whose contents can contain blank lines.
+    const RAW_TAGS: [&'static str; 4] = ["pre", "script", "style", "textarea"];
+    #[rustfmt::skip]
+    const BLOCK_TAGS: [&'static str; 62] = [
+        "address", "article", "aside", "base", "basefont", "blockquote", "body", "caption",
+        "center", "col", "colgroup", "dd", "details", "dialog", "dir", "div", "dl", "dt",
+        "fieldset", "figcaption", "figure", "footer", "form", "frame", "frameset", "h1", "h2",
+        "h3", "h4", "h5", "h6", "head", "header", "hr", "html", "iframe", "legend", "li", "link",
+        "main", "menu", "menuitem", "nav", "noframes", "ol", "optgroup", "option", "p", "param",
+        "search", "section", "summary", "table", "tbody", "td", "tfoot", "th", "thead", "title",
+        "tr", "track", "ul",
+    ];
+
+    /// Get the tag name at the start of `s` along with the rest of the string.
+    fn tag_name(s: &str) -> Option<(String, &str)> {
+        if !s.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let len = s
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+            .unwrap_or(s.len());
+
+        Some((s[..len].to_ascii_lowercase(), &s[len..]))
+    }
+
+    /// A tag name has to be followed by whitespace, the end of the line, `>` or `/>`.
+    fn is_tag_end(rest: &str) -> bool {
+        rest.is_empty() || rest.starts_with([' ', '\t', '>']) || rest.starts_with("/>")
+    }
+
+    /// Check if a line consists of only a complete opening or closing tag.
+    fn is_complete_tag(line: &str) -> bool {
+        let Some(tag) = line.trim_end().strip_prefix('<') else {
+            return false;
+        };
+        let closing = tag.starts_with('/');
+        let Some((_, rest)) = Self::tag_name(tag.strip_prefix('/').unwrap_or(tag)) else {
+            return false;
+        };
+        let Some(attributes) = rest.strip_suffix('>') else {
+            return false;
+        };
+        if closing {
+            return attributes.trim().is_empty();
+        }
+
+        (attributes.is_empty() || attributes.starts_with([' ', '\t', '/']))
+            && !attributes.contains(['<', '>'])
+    }
+
+    /// Try to open an HTML block. Complete tags that aren't block-level can't interrupt a
+    /// paragraph.
+    fn open(line: &str, in_paragraph: bool) -> Option<Self> {
+        let line = Taboc::strip_indent(line)?;
+        let tag = line.strip_prefix('<')?;
+
+        if let Some((name, rest)) = Self::tag_name(tag) {
+            if Self::RAW_TAGS.contains(&name.as_str())
+                && Self::is_tag_end(rest)
+                && !rest.starts_with("/>")
+            {
+                return Some(Self::Until(match name.as_str() {
+                    "pre" => "</pre>",
+                    "script" => "</script>",
+                    "style" => "</style>",
+                    _ => "</textarea>",
+                }));
+            }
+        }
+
+        let starts = [("!--", "-->"), ("?", "?>"), ("![CDATA[", "]]>")];
+        if let Some((_, end)) = starts.iter().find(|(start, _)| tag.starts_with(start)) {
+            return Some(Self::Until(end));
+        }
+        if tag
+            .strip_prefix('!')
+            .is_some_and(|t| t.starts_with(|c: char| c.is_ascii_alphabetic()))
+        {
+            return Some(Self::Until(">"));
+        }
+
+        let block_tag =
+            Self::tag_name(tag.strip_prefix('/').unwrap_or(tag)).is_some_and(|(name, rest)| {
+                Self::BLOCK_TAGS.contains(&name.as_str()) && Self::is_tag_end(rest)
+            });
+        if block_tag || (!in_paragraph && Self::is_complete_tag(line)) {
+            return Some(Self::UntilBlankLine);
+        }
+
+        None
+    }
+
+    /// Check if a line closes this HTML block. The closing line is still a part of the block
+    /// (unless it's blank).
+    fn is_closed_by(&self, line: &str) -> bool {
+        match self {
+            Self::Until(end) => line.to_ascii_lowercase().contains(end),
+            Self::UntilBlankLine => line.trim().is_empty(),
+        }
+    }
+}
+
+/// An open extension block of a markdown flavor (see [`Extensions`]). Lines starting with `#` in
+/// them aren't headings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ExtensionBlock {
+    /// `$$` display math, either on a line of its own (`$$ x^2 $$`) or starting with a bare `$$`
+    /// line. It ends at the first line ending with `$$`.
+    Math,
+    /// Pandoc `:::` fenced divs along with their nesting depth. Fences with attributes open a
+    /// (nested) div and bare ones close it.
+    Div(usize),
+    /// Liquid blocks. They end at their end tag.
+    Liquid(&'static str),
+}
+
+impl ExtensionBlock {
+    const MATH_DELIMITER: &'static str = "$$";
+    const DIV_MARKER: char = ':';
+    const DIV_MIN_LEN: usize = 3;
+    const LIQUID_TAG_START: &'static str = "{%";
+    const LIQUID_TAG_END: &'static str = "%}";
+    const LIQUID_BLOCKS: [(&'static str, &'static str); 3] = [
+        ("raw", "endraw"),
+        ("highlight", "endhighlight"),
+        ("comment", "endcomment"),
+    ];
+
+    /// Get the attributes of a fenced div line, allowing up to three spaces of indentation.
+    fn div_fence(line: &str) -> Option<&str> {
+        let line = Taboc::strip_indent(line)?;
+        let len = line.chars().take_while(|c| *c == Self::DIV_MARKER).count();
+
+        (len >= Self::DIV_MIN_LEN).then(|| line[len..].trim_matches([' ', '\t', ':']))
+    }
+
+    /// Get the name of the first Liquid tag in `s` (`{% name ... %}` or `{%- name ... -%}`) along
+    /// with the rest of the string after the tag.
+    fn liquid_tag(s: &str) -> Option<(&str, &str)> {
+        let start = s.find(Self::LIQUID_TAG_START)? + Self::LIQUID_TAG_START.len();
+        let tag = &s[start..];
+        let end = tag.find(Self::LIQUID_TAG_END)?;
+        let name = tag[..end]
+            .trim_start_matches('-')
+            .split_whitespace()
+            .next()
+            .unwrap_or_default();
+
+        Some((name, &tag[end + Self::LIQUID_TAG_END.len()..]))
+    }
+
+    /// Try to open an extension block. Returns the block and the rest of the opening line, which
+    /// can close it right away (e.g. `$$ x^2 $$`).
+    ///
+    /// Display math can't interrupt a paragraph, and a `$$` with text after its closing `$$` is
+    /// inline math (`$$E=mc^2$$ is famous.`).
+    fn open(line: &str, extensions: Extensions, in_paragraph: bool) -> Option<(Self, &str)> {
+        let stripped = Taboc::strip_indent(line)?;
+
+        if extensions.math && !in_paragraph {
+            if let Some(rest) = stripped.strip_prefix(Self::MATH_DELIMITER) {
+                let rest = rest.trim_end();
+                if rest.trim_start().is_empty() || rest.ends_with(Self::MATH_DELIMITER) {
+                    return Some((Self::Math, rest));
+                }
+            }
+        }
+        if extensions.fenced_divs && Self::div_fence(line).is_some_and(|attrs| !attrs.is_empty()) {
+            return Some((Self::Div(1), ""));
+        }
+        if extensions.liquid && stripped.starts_with(Self::LIQUID_TAG_START) {
+            let (name, rest) = Self::liquid_tag(stripped)?;
+            let (_, end) = Self::LIQUID_BLOCKS
+                .iter()
+                .find(|(start, _)| *start == name)?;
+            return Some((Self::Liquid(end), rest));
+        }
+
+        None
+    }
+
+    /// Check if a line closes this extension block. The closing line is still a part of the block.
+    fn is_closed_by(&mut self, line: &str) -> bool {
+        match self {
+            Self::Math => line.trim_end().ends_with(Self::MATH_DELIMITER),
+            Self::Div(depth) => {
+                match Self::div_fence(line) {
+                    Some("") => *depth -= 1,
+                    Some(_) => *depth += 1,
+                    None => {}
+                }
+                *depth == 0
+            }
+            Self::Liquid(end) => {
+                let mut rest = line;
+                while let Some((name, after)) = Self::liquid_tag(rest) {
+                    if name == *end {
+                        return true;
+                    }
+                    rest = after;
+                }
+                false
+            }
+        }
+    }
+}
+
+/// Table of contents opt-out markers. They're HTML comments, so they aren't rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Marker {
+    /// `<!-- omit in toc -->` (or `<!-- omit from toc -->`) and `<!-- taboc:ignore -->`. Omits
+    /// the heading on the same line or on the next one.
+    Ignore,
+    /// `<!-- taboc:ignore-start -->`. Omits every heading until the end marker.
+    IgnoreStart,
+    /// `<!-- taboc:ignore-end -->`.
+    IgnoreEnd,
+}
+
+impl Marker {
+    pub(crate) const COMMENT_START: &'static str = "<!--";
+    const COMMENT_END: &'static str = "-->";
+
+    /// Find the first marker comment in a line.
+    pub(crate) fn find(line: &str) -> Option<Self> {
+        let mut rest = line;
+
+        while let Some(start) = rest.find(Self::COMMENT_START) {
+            rest = &rest[start + Self::COMMENT_START.len()..];
+            let end = rest.find(Self::COMMENT_END)?;
+
+            match rest[..end].trim().to_ascii_lowercase().as_str() {
+                "omit in toc" | "omit from toc" | "taboc:ignore" => return Some(Self::Ignore),
+                "taboc:ignore-start" => return Some(Self::IgnoreStart),
+                "taboc:ignore-end" => return Some(Self::IgnoreEnd),
+                _ => rest = &rest[end + Self::COMMENT_END.len()..],
+            }
+        }
+
+        None
+    }
+}
 
 /// # Table of contents struct
 ///
@@ -28,151 +347,568 @@ use std::io::Read;
 /// ```
 pub struct Taboc {
     pub file: std::fs::File,
-    code_block: Cell<bool>,
+    path: Option<PathBuf>,
+    min_depth: usize,
     max_depth: usize,
+    skip_title: bool,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    front_matter_title: bool,
+    decoding: Decoding,
+    backend: Backend,
+    extensions: Extensions,
+    mdx: bool,
+    slugger: Box<dyn Slugger>,
+    list_style: ListStyle,
+    numbering: Option<Numbering>,
 }
 
+/// `Taboc` can be shared with and sent to other threads.
+const _: () = {
+    const fn assert_send<T: Send>() {}
+    const fn assert_sync<T: Sync>() {}
+    assert_send::<Taboc>();
+    assert_sync::<Taboc>();
+};
+
 impl Taboc {
     const MIN_HEADING: usize = 1;
+    const MAX_HEADING: usize = 6;
     const HEADING_CHAR: char = '#';
-    const CODE_BLOCK_STR: &'static str = "```";
+    /// Block elements can be indented by up to three spaces. Four spaces make an indented code
+    /// block.
+    const MAX_INDENT: usize = 3;
     const TOC_HEADING: &'static str = "## Table of contents";
+    const TOC_TITLE: &'static str = "Table of contents";
 
     pub fn new(file: std::fs::File, max_depth: usize) -> Self {
         Self {
             file,
-            code_block: Cell::new(false),
+            path: None,
+            min_depth: Self::MIN_HEADING,
             max_depth,
+            skip_title: false,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            front_matter_title: false,
+            decoding: Decoding::Strict,
+            backend: Backend::Scanner,
+            extensions: Flavor::default().extensions(),
+            mdx: false,
+            slugger: Box::new(SlugStyle::default()),
+            list_style: ListStyle::default(),
+            numbering: None,
         }
     }
 
-    /// See [`RFC3986`](https://www.rfc-editor.org/rfc/rfc3986).
-    fn percent_encode(c: char) -> String {
-        let mut utf8_bytes: [u8; 4] = [0u8; 4];
-        let bytes = c.encode_utf8(&mut utf8_bytes);
-        let mut encoded = String::with_capacity(bytes.len() * 3);
+    /// The path of the file, named in the errors about its contents. They only have the line and
+    /// the byte offset otherwise.
+    pub fn with_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.path = Some(path.as_ref().to_owned());
+        self
+    }
 
-        for byte in utf8_bytes {
-            if byte == 0 {
-                break;
-            }
-            encoded.push_str(&format!("%{:02X}", byte));
-        }
+    /// Min heading depth for the table of contents.
+    pub fn with_min_depth(mut self, min_depth: usize) -> Self {
+        self.min_depth = min_depth;
+        self
+    }
 
-        encoded
+    /// Don't include the first first-level heading (the title of the document).
+    pub fn with_skip_title(mut self, skip_title: bool) -> Self {
+        self.skip_title = skip_title;
+        self
     }
 
-    /// Make a Table of contents line based on the current heading level.
-    fn make_link(heading_name: &str) -> String {
-        let mut res = String::with_capacity(heading_name.len());
+    /// Only include the headings matching at least one of the patterns. Including everything if
+    /// there are none.
+    pub fn with_include(mut self, include: Vec<Pattern>) -> Self {
+        self.include = include;
+        self
+    }
 
-        /// Most are based on
-        /// [RFC3986#section-2.2](https://www.rfc-editor.org/rfc/rfc3986#section-2.2) with some
-        /// additional ones like: \`, `'`, `~`, `{` and `}`.
-        ///
-        /// NOTE: It's possible that not all excludable characters are covered. If you encounter a
-        /// issue with a missed one then feel free to post an issue on
-        /// [GitHub](https://github.com/1Git2Clone/taboc/issues).
-        const IGNORED_CHARACTERS: &[char] = &[
-            '+', ':', ';', '.', ',', '{', '}', '"', '@', '#', '>', '<', '[', ']', '|', '/', '?',
-            '!', '$', '*', '=', '&', '\'', '(', ')', '~',
-        ];
+    /// Exclude the headings matching any of the patterns along with their subsections.
+    pub fn with_exclude(mut self, exclude: Vec<Pattern>) -> Self {
+        self.exclude = exclude;
+        self
+    }
 
-        for c in heading_name.chars() {
-            if IGNORED_CHARACTERS.contains(&c) {
-                continue;
-            }
+    /// Use the `title` of the front matter as a first-level heading if the document doesn't have
+    /// one.
+    pub fn with_front_matter_title(mut self, front_matter_title: bool) -> Self {
+        self.front_matter_title = front_matter_title;
+        self
+    }
 
-            if c == ' ' {
-                res.push('-');
-                continue;
-            }
+    /// How the bytes of the file are decoded. Strict UTF-8 decoding by default.
+    pub fn with_decoding(mut self, decoding: Decoding) -> Self {
+        self.decoding = decoding;
+        self
+    }
 
-            if c.is_ascii() {
-                res.push(c.to_ascii_lowercase());
-                continue;
-            }
+    /// How the headings of the file are found. The built-in line scanner by default.
+    pub fn with_backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
 
-            if c.is_uppercase() {
-                res.push_str(&c.to_lowercase().to_string());
-                continue;
-            }
+    /// Skip the extension blocks of a markdown flavor. GitHub Flavored Markdown by default.
+    pub fn with_flavor(mut self, flavor: Flavor) -> Self {
+        self.extensions = flavor.extensions();
+        self
+    }
 
-            if !c.is_alphanumeric() {
-                res.push_str(&Self::percent_encode(c));
-                continue;
-            }
+    /// Enable or disable skipping an extension block, regardless of the flavor.
+    pub fn with_extension(mut self, extension: Extension, enabled: bool) -> Self {
+        self.extensions.set(extension, enabled);
+        self
+    }
+
+    /// Treat the file as an MDX document: skip its ESM, JSX and expression blocks and insert the
+    /// table of contents after the ESM preamble.
+    pub fn with_mdx(mut self, mdx: bool) -> Self {
+        self.mdx = mdx;
+        self
+    }
+
+    /// How the anchors of the headings are made. GitHub style slugs by default.
+    pub fn with_slugger<S: Slugger + 'static>(mut self, slugger: S) -> Self {
+        self.slugger = Box::new(slugger);
+        self
+    }
 
-            res.push(c);
+    /// How the table of contents list is rendered. Nested `-` bullets indented by two spaces by
+    /// default.
+    pub fn with_list_style(mut self, list_style: ListStyle) -> Self {
+        self.list_style = list_style;
+        self
+    }
+
+    /// Prefix the table of contents entries with their section numbers (`1.`, `1.1`, ...). They
+    /// aren't numbered by default.
+    pub fn with_numbering(mut self, numbering: Option<Numbering>) -> Self {
+        self.numbering = numbering;
+        self
+    }
+
+    /// Read the front matter from the start of the file.
+    pub fn front_matter(&self) -> Result<Option<FrontMatter>, Error> {
+        (&self.file).rewind()?;
+        let front_matter = FrontMatter::read(BufReader::new(&self.file))?;
+        (&self.file).rewind()?;
+
+        Ok(front_matter)
+    }
+
+    /// Make sure that the link is unique among the `links` of the document. Repeated links get a
+    /// `-1`, `-2`, ... suffix in the order they appear in (like GitHub does), or whatever the
+    /// slugger disambiguates them with.
+    ///
+    /// NOTE: A suffixed link can also collide with a heading that naturally ends with `-1`. The
+    /// suffix is incremented until the link is unique in that case, unless the slugger doesn't
+    /// check for collisions (GitHub doesn't).
+    fn unique_link(
+        links: &mut HashMap<String, usize>,
+        slugger: &dyn Slugger,
+        link: String,
+    ) -> String {
+        if !slugger.checks_collisions() {
+            return match links.get_mut(&link) {
+                Some(count) => {
+                    *count += 1;
+                    slugger.disambiguate(&link, *count)
+                }
+                None => {
+                    links.insert(link.clone(), 0);
+                    link
+                }
+            };
+        }
+
+        let mut unique = link.clone();
+        while links.contains_key(&unique) {
+            let count = links.entry(link.clone()).or_default();
+            *count += 1;
+            unique = slugger.disambiguate(&link, *count);
         }
+        links.insert(unique.clone(), 0);
 
-        res
+        unique
     }
 
-    /// Make a Table of contents line based on the current heading level.
-    fn make_line(heading_level: usize, line: &str) -> String {
+    /// Get the plain text of a heading and its link. Explicit ids (`{#id}` or `<a id="id">`) are
+    /// used verbatim, otherwise the link is made from the heading text (without the alt text of
+    /// the images, unless the slugger includes it) or its markdown source.
+    fn text_and_link(
+        links: &mut HashMap<String, usize>,
+        slugger: &dyn Slugger,
+        heading: &str,
+    ) -> (String, String) {
+        let (heading, id) = inline::heading_id(heading);
+        let text = inline::plain_text(heading).trim().to_owned();
+        let link = if slugger.slugs_raw_text() {
+            Self::link(links, slugger, heading.trim(), id)
+        } else if slugger.includes_image_alt() {
+            Self::link(links, slugger, &text, id)
+        } else {
+            Self::link(links, slugger, &inline::anchor_text(heading), id)
+        };
+
+        (text, link)
+    }
+
+    /// Get the links which are taken before any of the headings: the ids reserved by the slugger.
+    pub(crate) fn reserved_links(slugger: &dyn Slugger) -> HashMap<String, usize> {
+        slugger
+            .reserved()
+            .iter()
+            .map(|&id| (id.to_owned(), 0))
+            .collect()
+    }
+
+    /// Make the error of an invalid UTF-8 sequence, naming the file if its path is known.
+    fn invalid_utf8(path: Option<&Path>, line: usize, offset: u64) -> Error {
+        match path {
+            Some(path) => anyhow!(
+                "Invalid UTF-8 in `{}` on line {line} at byte offset {offset}.",
+                path.display()
+            ),
+            None => anyhow!("Invalid UTF-8 on line {line} at byte offset {offset}."),
+        }
+    }
+
+    /// Get the link of a heading from its plain text, unless it has an explicit id.
+    pub(crate) fn link(
+        links: &mut HashMap<String, usize>,
+        slugger: &dyn Slugger,
+        text: &str,
+        id: Option<String>,
+    ) -> String {
+        match id {
+            Some(id) => {
+                links.entry(id.clone()).or_default();
+                id
+            }
+            None => Self::unique_link(links, slugger, slugger.slug(text)),
+        }
+    }
+
+    /// Make a Table of contents line based on the indentation of the entry in the tree and its list
+    /// marker.
+    ///
+    /// The `text` is the plain text of the heading (without any inline markdown).
+    fn make_line(indent: &str, marker: &str, text: &str, link: &str) -> String {
         format!(
-            "{}- [{}](#{})\n",
-            "  ".repeat(heading_level - 1),
-            line,
-            Self::make_link(line)
+            "{indent}{marker} [{}](#{})\n",
+            inline::escape_link_text(text),
+            link
         )
     }
 
-    /// Check if a markdown line is valid.
-    fn valid_heading(&self, heading_level: usize, line: &str) -> bool {
-        if !(Self::MIN_HEADING..=self.max_depth).contains(&heading_level) {
+    /// Check if a markdown line is valid. The line shouldn't have any indentation.
+    ///
+    /// The opening `#` sequence has to be followed by a space, a tab or the end of the line.
+    /// Headings can be empty (e.g. `#`), those just don't get a table of contents entry.
+    fn valid_heading(heading_level: usize, line: &str) -> bool {
+        if !(Self::MIN_HEADING..=Self::MAX_HEADING).contains(&heading_level) {
             return false;
         }
-        if line.len() <= heading_level || line.chars().nth(heading_level) != Some(' ') {
-            return false;
+        let rest = &line[heading_level..];
+        rest.is_empty() || rest.starts_with([' ', '\t'])
+    }
+
+    /// Get the text of a valid ATX heading line without the opening and the optional closing `#`
+    /// sequence, and the surrounding whitespace.
+    ///
+    /// The closing sequence has to be preceded by a space or a tab, so `# C#` keeps its `#` and so
+    /// does an escaped `\#`.
+    pub(crate) fn atx_text(heading_level: usize, line: &str) -> &str {
+        let text = line[heading_level..].trim_matches([' ', '\t']);
+        let without_closing = text.trim_end_matches(Self::HEADING_CHAR);
+
+        if without_closing.is_empty() {
+            return without_closing;
         }
-        if line.chars().nth(heading_level + 1).is_none() {
+        if without_closing.ends_with([' ', '\t']) {
+            return without_closing.trim_end_matches([' ', '\t']);
+        }
+
+        text
+    }
+
+    /// Check if a heading level should be a part of the table of contents.
+    fn in_depth(&self, heading_level: usize) -> bool {
+        (self.min_depth.max(Self::MIN_HEADING)..=self.max_depth).contains(&heading_level)
+    }
+
+    /// Check if the heading text matches the include and exclude patterns. The exclude patterns
+    /// take precedence.
+    fn is_included(&self, text: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| p.is_match(text)))
+            && !self.is_excluded(text)
+    }
+
+    fn is_excluded(&self, text: &str) -> bool {
+        self.exclude.iter().any(|p| p.is_match(text))
+    }
+
+    /// Get the width of a line's indentation along with its length in bytes. Tabs are expanded to
+    /// the next multiple of 4 columns.
+    fn indent_width(line: &str) -> (usize, usize) {
+        let mut width = 0;
+        let mut len = 0;
+
+        for c in line.chars() {
+            match c {
+                ' ' => width += 1,
+                '\t' => width += 4 - width % 4,
+                _ => break,
+            }
+            len += 1;
+        }
+
+        (width, len)
+    }
+
+    /// Strip the (up to three spaces) indentation of a block element. Returns `None` if the line
+    /// is indented further than that.
+    pub(crate) fn strip_indent(line: &str) -> Option<&str> {
+        let (width, len) = Self::indent_width(line);
+        if width > Self::MAX_INDENT {
+            return None;
+        }
+        Some(&line[len..])
+    }
+
+    /// Get the heading level of a setext underline: `===` is a first-level heading and `---` is a
+    /// second-level one.
+    fn setext_level(line: &str) -> Option<usize> {
+        let underline = Self::strip_indent(line)?.trim_end();
+        let level = match underline.chars().next()? {
+            '=' => 1,
+            '-' => 2,
+            _ => return None,
+        };
+        let marker = underline.as_bytes()[0];
+
+        underline.bytes().all(|b| b == marker).then_some(level)
+    }
+
+    /// Thematic breaks are three or more matching `-`, `*` or `_` characters, optionally separated
+    /// by spaces or tabs.
+    fn is_thematic_break(line: &str) -> bool {
+        let Some(line) = Self::strip_indent(line) else {
+            return false;
+        };
+        let Some(marker) = line.chars().next().filter(|c| matches!(c, '-' | '*' | '_')) else {
             return false;
+        };
+
+        line.chars().all(|c| c == marker || c == ' ' || c == '\t')
+            && line.chars().filter(|c| *c == marker).count() >= 3
+    }
+
+    /// Block quotes and list items. Their contents can't be underlined to make a setext heading.
+    fn is_container_start(line: &str) -> bool {
+        Self::strip_indent(line).is_some_and(|l| l.starts_with('>'))
+            || Self::list_item_indent(line).is_some()
+    }
+
+    /// Get the indentation of a list item's content. Lines indented at least as much belong to the
+    /// list item, rather than being indented code blocks.
+    fn list_item_indent(line: &str) -> Option<usize> {
+        let (indent, _) = Self::indent_width(line);
+        let item = Self::strip_indent(line)?;
+
+        let digits = item.chars().take_while(char::is_ascii_digit).count();
+        let marker_len = match digits {
+            0 if item.starts_with(['-', '+', '*']) => 1,
+            1..=9 if item[digits..].starts_with(['.', ')']) => digits + 1,
+            _ => return None,
+        };
+
+        let rest = &item[marker_len..];
+        if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+            return None;
         }
-        true
+
+        // Content indented by 5 or more spaces is an indented code block with a single space of
+        // list item indentation.
+        let spaces = match Self::indent_width(rest).0 {
+            0 => 1,
+            n if n > Self::MAX_INDENT + 1 || rest.trim().is_empty() => 1,
+            n => n,
+        };
+
+        Some(indent + marker_len + spaces)
+    }
+
+    /// Iterate over the headings of the file.
+    ///
+    /// Headings in front matter, code blocks, HTML blocks and empty headings are skipped. The
+    /// headings aren't filtered by the max depth and the ones opted out of the table of contents
+    /// with a marker comment are only flagged as [`Heading::omitted`].
+    pub fn headings(&self) -> Result<Headings<'_>, Error> {
+        self.headings_after(Self::reserved_links(self.slugger.as_ref()))
     }
 
-    /// We shouldn't parse headings that are in code blocks: ```.
-    fn is_in_code_block(&self, line: &str) -> bool {
-        if line.starts_with(Self::CODE_BLOCK_STR) {
-            self.code_block.replace(!self.code_block.get());
+    /// Iterate over the headings of the file, given the links which are taken before its first
+    /// heading.
+    fn headings_after(&self, links: HashMap<String, usize>) -> Result<Headings<'_>, Error> {
+        let front_matter = self.front_matter()?;
+        let mut reader = BufReader::new(&self.file);
+        let mut line_buf = Vec::new();
+
+        let (line, offset) = front_matter.map_or((0, 0), |fm| (fm.lines, fm.len));
+        for _ in 0..line {
+            reader.read_until(b'\n', &mut line_buf)?;
+        }
+
+        let scanner = Scanner {
+            reader,
+            line_buf,
+            line,
+            offset,
+            code_block: None,
+            extension_block: None,
+            html_block: None,
+            paragraph: None,
+            in_container: false,
+            list_indent: None,
+            links,
+            omit_line: false,
+            omit_next: false,
+            in_ignored_region: false,
+            decoding: self.decoding,
+            extensions: self.extensions,
+            mdx: self.mdx.then(MdxBlocks::default),
+            slugger: self.slugger.as_ref(),
+            path: self.path.as_deref(),
+        };
+
+        match self.backend {
+            Backend::Scanner => Ok(Headings(HeadingsInner::Scanner(Box::new(scanner)))),
+            #[cfg(feature = "commonmark")]
+            Backend::CommonMark => {
+                let links = scanner.links.clone();
+                let document = scanner.read_document()?;
+                Ok(Headings(HeadingsInner::CommonMark(
+                    commonmark::headings(&document, self.slugger.as_ref(), links).into_iter(),
+                )))
+            }
         }
-        self.code_block.get()
     }
 
-    /// Make the table of contents based on a file.
-    pub fn parse(&self) -> Result<String, Error> {
-        let mut res = format!("\n\n{}\n\n", Self::TOC_HEADING);
+    /// Get the headings which are a part of the table of contents, based on the selection rules
+    /// (depth, include and exclude patterns, opt-out markers, ...).
+    pub fn selected_headings(&self) -> Result<Vec<Heading>, Error> {
+        let (mut res, has_title) = self.select(self.headings()?)?;
+
+        let front_matter = self.front_matter()?;
+        let title = front_matter.as_ref().and_then(|fm| fm.title.as_deref());
+        if let Some(title) = title.filter(|_| self.front_matter_title && !has_title) {
+            let text = inline::plain_text(title);
 
-        for l in BufReader::new(&self.file).lines() {
-            let line = l?;
+            // The title is rendered before the body, so the headings with the same slug get a
+            // suffix.
+            let slugger = self.slugger.as_ref();
+            let mut links = Self::reserved_links(slugger);
+            let slug = Self::unique_link(&mut links, slugger, slugger.slug(&text));
+            res = self.select(self.headings_after(links)?)?.0;
 
-            if self.is_in_code_block(&line) {
+            if self.in_depth(Self::MIN_HEADING) && !self.skip_title && self.is_included(&text) {
+                res.insert(
+                    0,
+                    Heading {
+                        level: Self::MIN_HEADING,
+                        raw: title.to_owned(),
+                        slug,
+                        text,
+                        line: 1,
+                        range: 0..front_matter.map_or(0, |fm| fm.len),
+                        omitted: false,
+                    },
+                );
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Select the headings which are a part of the table of contents. Also returns whether the
+    /// document has a first-level heading (a title).
+    fn select(&self, headings: Headings<'_>) -> Result<(Vec<Heading>, bool), Error> {
+        let mut res = Vec::new();
+        let mut has_title = false;
+        // The level of the last excluded heading. Its subsections are excluded too.
+        let mut excluded_level: Option<usize> = None;
+
+        for heading in headings {
+            let heading = heading?;
+
+            if excluded_level.is_some_and(|level| heading.level > level) {
                 continue;
             }
+            excluded_level = None;
 
-            let heading_count = line
-                .chars()
-                .take_while(|c| *c == Self::HEADING_CHAR)
-                .count();
+            if self.skip_title && !has_title && heading.level == Self::MIN_HEADING {
+                has_title = true;
+                continue;
+            }
+            has_title |= heading.level == Self::MIN_HEADING;
+
+            if self.is_excluded(&heading.text) {
+                excluded_level = Some(heading.level);
+                continue;
+            }
 
-            if !self.valid_heading(heading_count, &line) {
+            if !self.in_depth(heading.level) || !self.is_included(&heading.text) {
                 continue;
             }
 
-            if line.starts_with(Self::TOC_HEADING) {
+            if heading.omitted || (heading.level == 2 && heading.raw.starts_with(Self::TOC_TITLE)) {
                 continue;
             }
 
-            let heading = line
-                .chars()
-                .skip(heading_count)
-                .skip_while(|c| c.is_whitespace())
-                .collect::<String>();
+            res.push(heading);
+        }
+
+        Ok((res, has_title))
+    }
+
+    /// Get the table of contents as a tree of the selected headings.
+    pub fn tree(&self) -> Result<Vec<TocNode>, Error> {
+        Ok(TocNode::build(self.selected_headings()?))
+    }
+
+    /// Render the table of contents entries, indented based on their depth in the tree.
+    fn render(&self, res: &mut String, nodes: &[TocNode], indent: &str) {
+        for (i, node) in nodes.iter().enumerate() {
+            let marker = self.list_style.marker(i);
+            res.push_str(&Self::make_line(
+                indent,
+                &marker,
+                &node.heading.text,
+                &node.heading.slug,
+            ));
 
-            res.push_str(&Self::make_line(heading_count, &heading));
+            let child_indent = format!("{indent}{}", self.list_style.indent(&marker));
+            self.render(res, &node.children, &child_indent);
         }
+    }
+
+    /// Make the table of contents based on a file.
+    pub fn parse(&self) -> Result<String, Error> {
+        let mut res = format!("\n\n{}\n\n", Self::TOC_HEADING);
+
+        let mut nodes = self.tree()?;
+        if let Some(numbering) = &self.numbering {
+            numbering.number(&mut nodes);
+        }
+        if !self.list_style.nested {
+            nodes = TocNode::flatten(nodes);
+        }
+        self.render(&mut res, &nodes, "");
 
         // remove the trailing newline symbol.
         res.pop();
@@ -184,6 +920,9 @@ impl Taboc {
     ///
     /// NOTE: This ensures that there's no table of contents as the first second-level heading of a
     /// markdown document but it doesn't ensure it if it's located anywhere else.
+    ///
+    /// The table of contents is never inserted in the front matter of the document and it's
+    /// written with the line endings of the document.
     pub fn write_to_file<P: AsRef<Path>>(
         &self,
         path: P,
@@ -192,113 +931,620 @@ impl Taboc {
     ) -> Result<(), Error> {
         let mut target_file = OpenOptions::new().read(true).write(true).open(path)?;
 
-        let mut pos = 0;
-        let lookup_header = "## ";
-        let mut line_buf = Vec::new();
+        let text_format = TextFormat::detect(BufReader::new(&target_file))?;
+        target_file.rewind()?;
+        let start = FrontMatter::read(BufReader::new(&target_file))?
+            .map_or(text_format.bom_len(), |fm| fm.len);
+
         let mut reader = BufReader::new(&target_file);
+        reader.seek(SeekFrom::Start(start))?;
+        let lookup_header = b"## ";
+        let mut line_buf = Vec::new();
 
-        let mut already_exists = false;
+        let mut pos = start;
+        // The end of the last non-blank line, without its line ending.
+        let mut content_end = start;
+        // The start of the existing table of contents.
+        let mut existing: Option<u64> = None;
+        // Whether the table of contents is inserted right before a heading on the first line.
+        let mut separate = false;
+        // The ESM preamble of MDX documents (imports and exports) stays before the table of
+        // contents.
+        let mut in_preamble = self.mdx;
+        let mut in_esm = false;
 
-        while let Ok(char_count) = reader.read_until(b'\n', &mut line_buf) {
+        loop {
+            line_buf.clear();
+            let char_count = reader.read_until(b'\n', &mut line_buf)?;
             if char_count == 0 {
                 break;
             }
 
-            if line_buf.starts_with(lookup_header.as_bytes()) {
-                let windows_toc = line_buf[line_buf.len().saturating_sub(2)] != b'\r'
-                    && &line_buf[0..line_buf.len().saturating_sub(1)]
-                        == Self::TOC_HEADING.as_bytes();
-                let unix_toc = &line_buf[0..line_buf.len()] == Self::TOC_HEADING.as_bytes();
-                if !update_existing && (windows_toc || unix_toc) {
+            let line = LineEnding::strip(&line_buf);
+            if in_preamble {
+                let blank = line.trim_ascii().is_empty();
+                in_esm = if in_esm {
+                    !blank
+                } else {
+                    MdxBlocks::is_esm_start(&String::from_utf8_lossy(line))
+                };
+                in_preamble = in_esm || blank;
+            }
+
+            if existing.is_some() {
+                // The existing table of contents ends before the next heading.
+                if line.starts_with(b"#") {
+                    break;
+                }
+            } else if !in_preamble && line.starts_with(lookup_header) {
+                if line != Self::TOC_HEADING.as_bytes() {
+                    separate = content_end == pos;
+                    break;
+                }
+                if !update_existing {
                     return Err(
                         anyhow!("There's already a table of contents in the first heading of the second level of this file.")
                     );
-                } else if windows_toc || unix_toc {
-                    already_exists = true;
                 }
-                // I wish I had an explanation for the off-by-one error here.
-                pos -= lookup_header.len() as u64 - 1;
-                break;
+                existing = Some(content_end);
             }
 
+            if !line.trim_ascii().is_empty() {
+                content_end = pos + line.len() as u64;
+            }
             pos += char_count as u64;
+        }
 
-            line_buf.clear();
+        // The existing table of contents is replaced, up to the end of its last entry.
+        let (insert_pos, rest_pos) = (existing.unwrap_or(content_end), content_end);
+
+        let mut input = text_format.line_ending.convert(input).into_owned();
+        if separate {
+            input.push_str(&text_format.line_ending.as_str().repeat(2));
         }
+        let input = self.decoding.encode(&input);
 
-        target_file.seek(std::io::SeekFrom::Start(pos))?;
         #[cfg(feature = "memmap2")]
-        let rest_map = unsafe { MmapMut::map_mut(&target_file)? };
-        #[cfg(feature = "memmap2")]
-        let mut rest = &rest_map[..];
-        #[cfg(not(feature = "memmap2"))]
-        let mut rest = Vec::<u8>::new();
+        {
+            let len = target_file.metadata()?.len();
+            let new_len = insert_pos + input.len() as u64 + (len - rest_pos);
+            target_file.set_len(max(len, new_len))?;
+
+            let mut map = unsafe { MmapMut::map_mut(&target_file)? };
+            let insert_pos = insert_pos as usize;
+            map.copy_within(rest_pos as usize..len as usize, insert_pos + input.len());
+            map[insert_pos..insert_pos + input.len()].copy_from_slice(&input);
+            map.flush()?;
+            drop(map);
+
+            target_file.set_len(new_len)?;
+        }
         #[cfg(not(feature = "memmap2"))]
-        target_file.read_to_end(&mut rest)?;
+        {
+            target_file.seek(SeekFrom::Start(rest_pos))?;
+            let mut rest = Vec::<u8>::new();
+            target_file.read_to_end(&mut rest)?;
 
-        target_file.seek(std::io::SeekFrom::Start(pos))?;
+            target_file.seek(SeekFrom::Start(insert_pos))?;
+            target_file.write_all(&input)?;
+            target_file.write_all(&rest)?;
 
-        if already_exists {
-            let mut reader = BufReader::new(&target_file);
-            let mut drain_pos = 0;
+            let new_len = target_file.stream_position()?;
+            target_file.set_len(new_len)?;
+        }
 
-            // reads [`Self::TOC_HEADING`] twice. Log each line to understand why it's like this.
-            let mut end_heading_count = 3;
-            let mut last_line_char_count = 0;
+        Ok(())
+    }
 
-            while let Ok(char_count) = reader.read_until(b'\n', &mut line_buf) {
-                // println!("LINE: {}", String::from_utf8_lossy(&line_buf));
+    /// Find where the text of a heading starts on its first line: right after the `#` sequence of
+    /// ATX headings and after the indentation of setext ones.
+    fn text_offset(line: &str, raw: &str) -> Option<usize> {
+        line.find(raw).or_else(|| {
+            let content = line.trim();
+            (!content.is_empty() && raw.starts_with(content))
+                .then(|| line.len() - line.trim_start().len())
+        })
+    }
 
-                if line_buf.starts_with(b"#") {
-                    end_heading_count -= 1;
-                } else if end_heading_count == 0 {
-                    drain_pos -= last_line_char_count;
-                    break;
-                }
-                if line_buf.trim_ascii().is_empty() && end_heading_count == 1 {
-                    drain_pos -= char_count;
+    /// Insert explicit ids (`<a id="slug"></a>`) at the start of the headings of the table of
+    /// contents which don't have one, so the anchors are the same wherever the document is
+    /// rendered. Returns how many ids were inserted.
+    ///
+    /// The explicit ids are used verbatim afterwards, so the table of contents links stay the same.
+    pub fn insert_ids<P: AsRef<Path>>(&self, path: P) -> Result<usize, Error> {
+        let body_start = self.front_matter()?.map_or(0, |fm| fm.len);
+        let headings = self.selected_headings()?;
+        let source = std::fs::read(&path)?;
+
+        let mut res = Vec::with_capacity(source.len());
+        let mut pos = 0;
+        let mut count = 0;
+        for heading in headings {
+            // The front matter title isn't a heading of the document.
+            if heading.range.start < body_start || inline::heading_id(&heading.raw).1.is_some() {
+                continue;
+            }
+
+            let start = heading.range.start as usize;
+            let end = heading.range.end as usize;
+            let line_end = source[start..end]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(end, |len| start + len);
+            let line = self
+                .decoding
+                .decode(LineEnding::strip(&source[start..line_end]))
+                .map_err(|e| {
+                    Self::invalid_utf8(
+                        Some(path.as_ref()),
+                        heading.line,
+                        (start + e.valid_up_to()) as u64,
+                    )
+                })?;
+            // The offset is in the decoded line, which doesn't have the length of the source one.
+            let insert_pos = Self::text_offset(&line, &heading.raw)
+                .and_then(|offset| {
+                    self.decoding
+                        .source_len(LineEnding::strip(&source[start..line_end]), &line[..offset])
+                })
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Couldn't find the text of the heading on line {}.",
+                        heading.line
+                    )
+                })?
+                + start;
+            res.extend_from_slice(&source[pos..insert_pos]);
+            res.extend_from_slice(
+                &self
+                    .decoding
+                    .encode(&format!("<a id=\"{}\"></a>", heading.slug)),
+            );
+            pos = insert_pos;
+            count += 1;
+        }
+        res.extend_from_slice(&source[pos..]);
+
+        if count > 0 {
+            std::fs::write(path, res)?;
+        }
+
+        Ok(count)
+    }
+}
+
+/// The paragraph preceding the current line. Setext headings are paragraphs followed by an
+/// underline.
+struct Paragraph {
+    raw: String,
+    line: usize,
+    start: u64,
+    omitted: bool,
+}
+
+/// An iterator over the headings of a file. Made with [`Taboc::headings`].
+pub struct Headings<'a>(HeadingsInner<'a>);
+
+enum HeadingsInner<'a> {
+    Scanner(Box<Scanner<'a>>),
+    /// The whole document is parsed at once.
+    #[cfg(feature = "commonmark")]
+    CommonMark(std::vec::IntoIter<Heading>),
+}
+
+impl Iterator for Headings<'_> {
+    type Item = Result<Heading, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            HeadingsInner::Scanner(scanner) => scanner.next(),
+            #[cfg(feature = "commonmark")]
+            HeadingsInner::CommonMark(headings) => headings.next().map(Ok),
+        }
+    }
+}
+
+/// The built-in line scanner. It finds the headings line by line, keeping track of the blocks
+/// they can't be in.
+struct Scanner<'a> {
+    reader: BufReader<&'a File>,
+    line_buf: Vec<u8>,
+    /// The 1-based number of the last read line.
+    line: usize,
+    /// The byte offset of the next line.
+    offset: u64,
+    code_block: Option<Fence>,
+    extension_block: Option<ExtensionBlock>,
+    html_block: Option<HtmlBlock>,
+    paragraph: Option<Paragraph>,
+    /// Lazy continuation lines of a list item or a block quote aren't setext heading content.
+    in_container: bool,
+    /// The content indentation of the last list item.
+    list_indent: Option<usize>,
+    /// Every heading has an anchor (even if it's not a part of the table of contents).
+    links: HashMap<String, usize>,
+    /// The previous line is an ignore marker, so the current line is opted out of the table of
+    /// contents.
+    omit_line: bool,
+    /// The current line is an ignore marker.
+    omit_next: bool,
+    /// Between the `taboc:ignore-start` and the `taboc:ignore-end` markers.
+    in_ignored_region: bool,
+    decoding: Decoding,
+    extensions: Extensions,
+    mdx: Option<MdxBlocks>,
+    slugger: &'a dyn Slugger,
+    path: Option<&'a Path>,
+}
+
+impl Scanner<'_> {
+    /// Read the next line without its line ending, along with its byte range. The byte order mark
+    /// isn't a part of the first line.
+    fn read_line(&mut self) -> Result<Option<(String, Range<u64>)>, Error> {
+        self.line_buf.clear();
+        let char_count = self.reader.read_until(b'\n', &mut self.line_buf)?;
+        if char_count == 0 {
+            return Ok(None);
+        }
+
+        let mut start = self.offset;
+        self.offset += char_count as u64;
+        self.line += 1;
+
+        let mut line_buf = LineEnding::strip(&self.line_buf);
+        if start == 0 {
+            if let Some(rest) = line_buf.strip_prefix(BOM) {
+                line_buf = rest;
+                start += BOM.len() as u64;
+            }
+        }
+        let line = self.decoding.decode(line_buf).map_err(|e| {
+            Taboc::invalid_utf8(self.path, self.line, start + e.valid_up_to() as u64)
+        })?;
+
+        Ok(Some((
+            line.into_owned(),
+            start..start + line_buf.len() as u64,
+        )))
+    }
+
+    /// Read the rest of the document for the parser backends.
+    #[cfg(feature = "commonmark")]
+    fn read_document(mut self) -> Result<Document, Error> {
+        let mut document = Document::default();
+        while let Some((line, range)) = self.read_line()? {
+            let in_mdx_block = self.code_block.is_none() && self.is_in_mdx_block(&line);
+            let in_code_block = !in_mdx_block && self.is_in_code_block(&line);
+            let in_extension_block =
+                !in_mdx_block && !in_code_block && self.is_in_extension_block(&line);
+
+            // The rest of the lines are still scanned to keep track of the paragraphs, which some
+            // of the blocks can't interrupt.
+            if in_mdx_block || in_code_block || in_extension_block {
+                self.end_paragraph();
+            } else {
+                self.scan_markdown(&line, range.clone());
+            }
+
+            // The parser doesn't know about the MDX and the extension blocks, so they're left out.
+            let skipped = in_mdx_block || in_extension_block;
+            document.push_line(if skipped { "" } else { &line }, self.line, range);
+        }
+
+        Ok(document)
+    }
+
+    /// Make a heading out of its raw text. Empty headings are skipped.
+    fn heading(
+        &mut self,
+        level: usize,
+        raw: &str,
+        line: usize,
+        range: Range<u64>,
+        omitted: bool,
+    ) -> Option<Heading> {
+        if raw.is_empty() {
+            return None;
+        }
+        let (text, slug) = Taboc::text_and_link(&mut self.links, self.slugger, raw);
+
+        Some(Heading {
+            level,
+            raw: raw.to_owned(),
+            text,
+            slug,
+            line,
+            range,
+            omitted: omitted || self.in_ignored_region || Marker::find(raw) == Some(Marker::Ignore),
+        })
+    }
+
+    /// Add a line to the current paragraph or start a new one.
+    fn push_paragraph(&mut self, text: &str, range: &Range<u64>, in_list_item: bool) {
+        match self.paragraph.as_mut() {
+            Some(p) => {
+                p.raw.push(' ');
+                p.raw.push_str(text);
+            }
+            None => {
+                self.paragraph = Some(Paragraph {
+                    raw: text.to_owned(),
+                    line: self.line,
+                    start: range.start,
+                    omitted: self.omit_line,
+                });
+                self.in_container = in_list_item;
+            }
+        }
+    }
+
+    /// Start a paragraph inside of a list item or a block quote.
+    fn push_container_paragraph(&mut self, range: &Range<u64>) {
+        self.push_paragraph("", range, true);
+        self.in_container = true;
+    }
+
+    /// End the current paragraph (and leave the container it's in).
+    fn end_paragraph(&mut self) {
+        self.paragraph = None;
+        self.in_container = false;
+    }
+
+    /// We shouldn't parse headings that are in fenced code blocks: ``` or ~~~.
+    ///
+    /// NOTE: The fence lines themselves are considered a part of the code block. An unclosed fence
+    /// lasts until the end of the document.
+    fn is_in_code_block(&mut self, line: &str) -> bool {
+        match self.code_block {
+            Some(fence) => {
+                if fence.is_closed_by(line) {
+                    self.code_block = None;
                 }
+                true
+            }
+            None => {
+                self.code_block = Fence::open(line);
+                self.code_block.is_some()
+            }
+        }
+    }
 
-                drain_pos += char_count;
+    /// We shouldn't parse headings that are in the extension blocks of the markdown flavor.
+    fn is_in_extension_block(&mut self, line: &str) -> bool {
+        if let Some(block) = self.extension_block.as_mut() {
+            if block.is_closed_by(line) {
+                self.extension_block = None;
+            }
+            return true;
+        }
 
-                line_buf.clear();
-                last_line_char_count = char_count;
+        let in_paragraph = self.paragraph.is_some();
+        let Some((mut block, rest)) = ExtensionBlock::open(line, self.extensions, in_paragraph)
+        else {
+            return false;
+        };
+        self.extension_block = Some(block).filter(|_| !block.is_closed_by(rest));
+        true
+    }
+
+    /// We shouldn't parse headings that are in the ESM, JSX or expression blocks of MDX documents.
+    fn is_in_mdx_block(&mut self, line: &str) -> bool {
+        let in_paragraph = self.paragraph.is_some();
+        self.mdx
+            .as_mut()
+            .is_some_and(|mdx| mdx.skip(line, in_paragraph))
+    }
+
+    /// We shouldn't parse headings that are in HTML comments or raw HTML blocks.
+    fn is_in_html_block(&mut self, line: &str) -> bool {
+        let block = match self.html_block {
+            Some(block) => block,
+            None => match HtmlBlock::open(line, self.paragraph.is_some()) {
+                Some(block) => block,
+                None => return false,
+            },
+        };
+
+        self.html_block = Some(block).filter(|b| !b.is_closed_by(line));
+        true
+    }
+
+    /// Scan a line of the document. Returns the heading that ends on this line, if there's one.
+    fn scan(&mut self, line: &str, range: Range<u64>) -> Option<Heading> {
+        // MDX blocks can contain code fences (in template literals), so they're checked first.
+        if (self.code_block.is_none() && self.is_in_mdx_block(line))
+            || self.is_in_code_block(line)
+            || self.is_in_extension_block(line)
+        {
+            self.end_paragraph();
+            return None;
+        }
+
+        self.scan_markdown(line, range)
+    }
+
+    /// Scan a line of the document which isn't in an MDX, code or extension block.
+    fn scan_markdown(&mut self, line: &str, range: Range<u64>) -> Option<Heading> {
+        // Only standalone comments are markers (and the heading line itself).
+        let marker = Taboc::strip_indent(line)
+            .filter(|l| l.starts_with(Marker::COMMENT_START))
+            .and_then(|_| Marker::find(line));
+        match marker {
+            Some(Marker::IgnoreStart) => self.in_ignored_region = true,
+            Some(Marker::IgnoreEnd) => self.in_ignored_region = false,
+            _ => {}
+        }
+        self.omit_line = std::mem::replace(&mut self.omit_next, marker == Some(Marker::Ignore));
+
+        if self.is_in_html_block(line) || line.trim().is_empty() {
+            self.end_paragraph();
+            return None;
+        }
+
+        let (indent, _) = Taboc::indent_width(line);
+        let in_list_item = self.list_indent.is_some_and(|content| indent >= content);
+        // Lazy continuation lines don't close the list item.
+        let lazy = self.in_container && self.paragraph.is_some();
+        if !(in_list_item || lazy) {
+            self.list_indent = None;
+        }
+
+        let Some(stripped) = Taboc::strip_indent(line) else {
+            // Indented code blocks can't interrupt a paragraph. Outside of paragraphs and list
+            // items they're skipped entirely.
+            if self.paragraph.is_some() {
+                self.push_paragraph(line.trim(), &range, in_list_item);
+            } else if in_list_item {
+                self.push_container_paragraph(&range);
             }
+            return None;
+        };
 
-            #[cfg(feature = "memmap2")]
-            {
-                rest = &rest_map[min(drain_pos - 1, rest_map.len() - 1)..];
+        if !self.in_container {
+            if let Some(level) = Taboc::setext_level(line) {
+                if let Some(paragraph) = self.paragraph.take() {
+                    let range = paragraph.start..range.end;
+                    let omitted = paragraph.omitted || self.omit_line;
+                    return self.heading(level, &paragraph.raw, paragraph.line, range, omitted);
+                }
             }
-            #[cfg(not(feature = "memmap2"))]
-            rest.drain(..drain_pos);
         }
 
-        target_file.seek(std::io::SeekFrom::Start(pos))?;
-        target_file.write_all(input.as_bytes())?;
-        #[cfg(feature = "memmap2")]
-        target_file.write_all(rest)?;
-        #[cfg(feature = "memmap2")]
-        rest_map.flush()?;
-        #[cfg(not(feature = "memmap2"))]
-        target_file.write_all(&rest)?;
+        if Taboc::is_thematic_break(line) {
+            self.end_paragraph();
+            return None;
+        }
 
-        Ok(())
+        if Taboc::is_container_start(line) {
+            if let Some(content) = Taboc::list_item_indent(line) {
+                self.list_indent = Some(content);
+            }
+            self.end_paragraph();
+            self.push_container_paragraph(&range);
+            return None;
+        }
+
+        let level = stripped
+            .chars()
+            .take_while(|c| *c == Taboc::HEADING_CHAR)
+            .count();
+
+        if !Taboc::valid_heading(level, stripped) {
+            self.push_paragraph(stripped.trim(), &range, in_list_item);
+            return None;
+        }
+
+        self.end_paragraph();
+        let omitted = self.omit_line;
+        self.heading(
+            level,
+            Taboc::atx_text(level, stripped),
+            self.line,
+            range,
+            omitted,
+        )
+    }
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = Result<Heading, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (line, range) = match self.read_line() {
+                Ok(Some(line)) => line,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if let Some(heading) = self.scan(&line, range) {
+                return Some(Ok(heading));
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Taboc;
+    use super::{ExtensionBlock, Taboc};
+    use crate::utils::{
+        flavor::Flavor,
+        slug::{GitHub, GitLab},
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn unique_link() {
+        let mut links = HashMap::new();
+        let mut unique = |link: &str| Taboc::unique_link(&mut links, &GitLab, link.to_owned());
+
+        assert_eq!(unique("example"), "example");
+        assert_eq!(unique("example"), "example-1");
+        assert_eq!(unique("example-1"), "example-1-1");
+        assert_eq!(unique("example"), "example-2");
+        assert_eq!(unique("foo-1"), "foo-1");
+        assert_eq!(unique("foo"), "foo");
+        assert_eq!(unique("foo"), "foo-2");
+
+        // GitHub only counts the repeats of the same slug.
+        let mut links = HashMap::new();
+        let mut unique = |link: &str| Taboc::unique_link(&mut links, &GitHub, link.to_owned());
+
+        assert_eq!(unique("example"), "example");
+        assert_eq!(unique("example"), "example-1");
+        assert_eq!(unique("example-1"), "example-1");
+        assert_eq!(unique("example"), "example-2");
+    }
+
+    #[test]
+    fn atx_text() {
+        assert_eq!(Taboc::atx_text(2, "## Install ##"), "Install");
+        assert_eq!(Taboc::atx_text(2, "##   Install  \t"), "Install");
+        assert_eq!(Taboc::atx_text(1, "# Install ##########"), "Install");
+        assert_eq!(Taboc::atx_text(1, "# C#"), "C#");
+        assert_eq!(Taboc::atx_text(1, "# C# #"), "C#");
+        assert_eq!(Taboc::atx_text(1, "# Escaped \\#"), "Escaped \\#");
+        assert_eq!(Taboc::atx_text(3, "### ###"), "");
+        assert_eq!(Taboc::atx_text(1, "#"), "");
+    }
 
     #[test]
-    fn percent_encode() {
-        assert_eq!(Taboc::percent_encode('😁'), "%F0%9F%98%81");
-        assert_eq!(Taboc::percent_encode('♊'), "%E2%99%8A");
-        assert_eq!(Taboc::percent_encode('⏳'), "%E2%8F%B3");
-        assert_eq!(Taboc::percent_encode('❌'), "%E2%9D%8C");
-        assert_eq!(Taboc::percent_encode('⏪'), "%E2%8F%AA");
-        assert_eq!(Taboc::percent_encode('⛪'), "%E2%9B%AA");
-        assert_eq!(Taboc::percent_encode('⟣'), "%E2%9F%A3");
-        assert_eq!(Taboc::percent_encode('⛟'), "%E2%9B%9F");
+    fn text_offset() {
+        assert_eq!(Taboc::text_offset("## Install ##", "Install"), Some(3));
+        assert_eq!(Taboc::text_offset("# C# #", "C#"), Some(2));
+        assert_eq!(Taboc::text_offset("  Setext", "Setext heading"), Some(2));
+        assert_eq!(Taboc::text_offset("---", "Setext"), None);
+    }
+
+    #[test]
+    fn extension_block() {
+        let pandoc = Flavor::Pandoc.extensions();
+        let jekyll = Flavor::Jekyll.extensions();
+        let open = |line, extensions| ExtensionBlock::open(line, extensions, false).map(|(b, _)| b);
+
+        assert_eq!(open("$$", pandoc), Some(ExtensionBlock::Math));
+        assert_eq!(open("$$ x^2 $$", pandoc), Some(ExtensionBlock::Math));
+        assert_eq!(open("    $$", pandoc), None);
+        assert_eq!(open("$$E=mc^2$$ is famous.", pandoc), None);
+        assert_eq!(open("$$ x^2", pandoc), None);
+        assert!(ExtensionBlock::open("$$", pandoc, true).is_none());
+        assert_eq!(open("::: {.note}", pandoc), Some(ExtensionBlock::Div(1)));
+        assert_eq!(open(":::", pandoc), None);
+        assert_eq!(open("::: note", jekyll), None);
+        assert_eq!(
+            open("{%- highlight rust -%}", jekyll),
+            Some(ExtensionBlock::Liquid("endhighlight"))
+        );
+        assert_eq!(open("{% include note.html %}", jekyll), None);
+
+        assert!(ExtensionBlock::Math.is_closed_by("x^2 $$"));
+        assert!(!ExtensionBlock::Math.is_closed_by("$x$ + 1"));
+        assert!(ExtensionBlock::Liquid("endraw").is_closed_by("text {% endraw %}"));
+        assert!(!ExtensionBlock::Liquid("endraw").is_closed_by("{% raw %}"));
+
+        let mut div = ExtensionBlock::Div(1);
+        assert!(!div.is_closed_by("::::: nested"));
+        assert!(!div.is_closed_by(":::::"));
+        assert!(div.is_closed_by(":::"));
     }
 }
@@ -0,0 +1,148 @@
+/*!
+Regenerates the anchors of the GitHub golden table (`mock_data/github_slugs.tsv`) from GitHub's own
+renders.
+
+The headings of the table are rendered in a single document with the
+[GitHub markdown API](https://docs.github.com/en/rest/markdown/markdown) (in order, so the repeated
+ones are disambiguated like in the tests) and their anchors replace the second column. The rows
+whose anchor changed are printed, so the slugger can be fixed to match them.
+
+```sh
+cargo run --manifest-path utils/github_slugs/Cargo.toml -- mock_data/github_slugs.tsv
+```
+
+It needs `curl`. Set `GITHUB_TOKEN` to avoid the rate limit of anonymous requests.
+*/
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+type Error = Box<dyn std::error::Error>;
+
+const API_URL: &str = "https://api.github.com/markdown";
+const ID_PREFIX: &str = "id=\"user-content-";
+const PROVENANCE: &str = "# Provenance:";
+
+/// Escape a string as a JSON string literal.
+fn json_string(s: &str) -> String {
+    let mut res = String::with_capacity(s.len() + 2);
+    res.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\t' => res.push_str("\\t"),
+            c if c.is_control() => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+
+    res
+}
+
+/// Decode the HTML entities GitHub escapes attribute values with.
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Render a markdown document with the GitHub markdown API.
+fn render(markdown: &str) -> Result<String, Error> {
+    let mut curl = Command::new("curl");
+    curl.args(["-sS", "--fail", "-X", "POST", API_URL])
+        .args(["-H", "Accept: application/vnd.github+json"])
+        .args(["--data-binary", "@-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped());
+    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+        curl.args(["-H", &format!("Authorization: Bearer {token}")]);
+    }
+
+    let mut child = curl.spawn()?;
+    let body = format!(
+        "{{\"text\":{},\"mode\":\"markdown\"}}",
+        json_string(markdown)
+    );
+    child
+        .stdin
+        .take()
+        .ok_or("Couldn't write to curl.")?
+        .write_all(body.as_bytes())?;
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(format!("The GitHub markdown API request failed: {}.", output.status).into());
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Get the anchors of the headings of a rendered document, in order.
+fn anchors(html: &str) -> Vec<String> {
+    html.match_indices(ID_PREFIX)
+        .filter_map(|(start, _)| {
+            let id = &html[start + ID_PREFIX.len()..];
+            id.find('"').map(|end| decode_entities(&id[..end]))
+        })
+        .collect()
+}
+
+fn main() -> Result<(), Error> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "mock_data/github_slugs.tsv".to_owned());
+    let table = std::fs::read_to_string(&path)?;
+
+    let headings = table
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.is_empty())
+        .map(|line| line.split_once('\t').map(|(heading, _)| heading))
+        .collect::<Option<Vec<_>>>()
+        .ok_or("Every row needs a heading and an anchor.")?;
+    let markdown = headings
+        .iter()
+        .map(|heading| format!("## {heading}\n\n"))
+        .collect::<String>();
+
+    let anchors = anchors(&render(&markdown)?);
+    if anchors.len() != headings.len() {
+        return Err(format!(
+            "GitHub rendered {} anchors for {} headings.",
+            anchors.len(),
+            headings.len()
+        )
+        .into());
+    }
+
+    let mut anchors = anchors.into_iter();
+    let mut res = String::with_capacity(table.len());
+    for line in table.lines() {
+        if line.starts_with(PROVENANCE) {
+            res.push_str(&format!(
+                "{PROVENANCE} rendered by the GitHub markdown API (`utils/github_slugs`).\n"
+            ));
+            continue;
+        }
+        let Some((heading, old)) = line.split_once('\t').filter(|_| !line.starts_with('#')) else {
+            res.push_str(line);
+            res.push('\n');
+            continue;
+        };
+
+        let anchor = anchors.next().ok_or("Missing anchor.")?;
+        if anchor != old {
+            eprintln!("{heading:?}: {old:?} -> {anchor:?}");
+        }
+        res.push_str(&format!("{heading}\t{anchor}\n"));
+    }
+    std::fs::write(&path, res)?;
+
+    Ok(())
+}